@@ -11,6 +11,10 @@ const PROGRAM_ID = new PublicKey('HK92WjG3LE4JCWi5mU7pAvLz1JFBUwwh1v3obB9ajwnE')
 const MAGIC_PROGRAM_ID = new PublicKey('Magic11111111111111111111111111111111111111');
 const MAGIC_CONTEXT_ID = new PublicKey('MagicContext1111111111111111111111111111111');
 const DELEGATION_PROGRAM_ID = new PublicKey('DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh');
+const PERMISSION_PROGRAM_ID = new PublicKey('ACLseoPoyC3cBqoUtkbjZ4aDrkurZW86v19pXz2XQnp1');
+// Devnet Asia rollup, the validator auctions delegate to when none is set.
+const ER_ENDPOINT = 'https://devnet-as.magicblock.app';
+const ER_VALIDATOR = new PublicKey('MAS1Dt9qreoRMQ14YQuhg8UTZMMzDdKhmkZMECCzk57');
 const EPHEMERAL_ROLLUPS_PROGRAM_ID = new PublicKey(
   new Uint8Array([
     242, 96, 133, 63, 142, 184, 10, 176, 73, 157, 225, 152, 140, 130, 123, 23, 164, 195, 182,
@@ -37,9 +41,24 @@ function parsePubkey(input: string): PublicKey | null {
   }
 }
 
+// Mirrors utils::bid_commitment: sha256(amount_le || salt || bidder).
+async function bidCommitment(amount: BN, salt: Uint8Array, bidder: PublicKey): Promise<number[]> {
+  const preimage = Buffer.concat([amount.toArrayLike(Buffer, 'le', 8), Buffer.from(salt), bidder.toBuffer()]);
+  return Array.from(new Uint8Array(await crypto.subtle.digest('SHA-256', preimage)));
+}
+
+// The reveal needs the same amount and salt as the commitment, so both are kept
+// in local storage under the sealed bid until then.
+const saltKey = (sealedBid: PublicKey) => `ephemeralbid:bid:${sealedBid.toBase58()}`;
+
+function pda(seeds: Buffer[], programId: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, programId)[0];
+}
+
 export default function AuctionPage() {
   const wallet = useWallet();
   const [program, setProgram] = useState<Program<Ephemeralbid> | null>(null);
+  const [erProgram, setErProgram] = useState<Program<Ephemeralbid> | null>(null);
   const [flowMode, setFlowMode] = useState<FlowMode>('l1');
 
   const [auctionId, setAuctionId] = useState('1');
@@ -47,6 +66,7 @@ export default function AuctionPage() {
   const [minBid, setMinBid] = useState('1000000');
   const [minIncrement, setMinIncrement] = useState('100000');
   const [duration, setDuration] = useState('3600');
+  const [revealDuration, setRevealDuration] = useState('3600');
   const [bidAmount, setBidAmount] = useState('1200000');
   const [settleBidder, setSettleBidder] = useState('');
   const [status, setStatus] = useState<ResultState>({ message: 'Connect wallet to begin' });
@@ -54,21 +74,25 @@ export default function AuctionPage() {
   useEffect(() => {
     if (!wallet.publicKey || !wallet.signTransaction || !wallet.signAllTransactions) {
       setProgram(null);
+      setErProgram(null);
       setStatus({ message: 'Connect wallet first' });
       return;
     }
 
-    const provider = new AnchorProvider(
-      new Connection('https://api.devnet.solana.com', 'confirmed'),
-      {
-        publicKey: wallet.publicKey,
-        signTransaction: wallet.signTransaction,
-        signAllTransactions: wallet.signAllTransactions,
-      },
-      { commitment: 'confirmed' }
-    );
+    const signer = {
+      publicKey: wallet.publicKey,
+      signTransaction: wallet.signTransaction,
+      signAllTransactions: wallet.signAllTransactions,
+    };
+    const provider = new AnchorProvider(new Connection('https://api.devnet.solana.com', 'confirmed'), signer, {
+      commitment: 'confirmed',
+    });
+    const erProvider = new AnchorProvider(new Connection(ER_ENDPOINT, 'confirmed'), signer, {
+      commitment: 'confirmed',
+    });
 
     setProgram(new Program<Ephemeralbid>(IDL, provider));
+    setErProgram(new Program<Ephemeralbid>(IDL, erProvider));
     setStatus({ message: 'Wallet connected. Ready.' });
   }, [wallet.publicKey, wallet.signTransaction, wallet.signAllTransactions]);
 
@@ -127,42 +151,33 @@ export default function AuctionPage() {
     )[0];
   }, [auctionHouse, settleBidderPk]);
 
-  const bufferSealedBid = useMemo(() => {
-    if (!sealedBid) return null;
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from('buffer'), sealedBid.toBuffer()],
-      EPHEMERAL_ROLLUPS_PROGRAM_ID
-    )[0];
-  }, [sealedBid]);
-
-  const delegationRecordSealedBid = useMemo(() => {
-    if (!sealedBid) return null;
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from('delegation'), sealedBid.toBuffer()],
-      DELEGATION_PROGRAM_ID
-    )[0];
-  }, [sealedBid]);
+  const configPda = useMemo(() => pda([Buffer.from('config')], PROGRAM_ID), []);
 
-  const delegationMetadataSealedBid = useMemo(() => {
+  // Sealed bids only delegate behind a delegated permission that keeps them
+  // readable by the bidder alone.
+  const permission = useMemo(() => {
     if (!sealedBid) return null;
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from('delegation-metadata'), sealedBid.toBuffer()],
-      DELEGATION_PROGRAM_ID
-    )[0];
+    return pda([Buffer.from('permission:'), sealedBid.toBuffer()], PERMISSION_PROGRAM_ID);
   }, [sealedBid]);
 
-  const invoke = async (instruction: string, args: unknown[], accounts: Record<string, PublicKey>) => {
-    if (!program) throw new Error('Program not ready');
+  // Sealed bids are written on L1 or, once delegated, inside the rollup.
+  const invoke = async (
+    instruction: string,
+    args: unknown[],
+    accounts: Record<string, PublicKey | null>,
+    target: Program<Ephemeralbid> | null = program
+  ) => {
+    if (!target) throw new Error('Program not ready');
 
     const methodName = [instruction, toCamel(instruction)].find(
-      (name) => typeof (program.methods as any)[name] === 'function'
+      (name) => typeof (target.methods as any)[name] === 'function'
     );
 
     if (!methodName) {
-      throw new Error(`Method "${instruction}" not found. Available: ${Object.keys(program.methods).join(', ')}`);
+      throw new Error(`Method "${instruction}" not found. Available: ${Object.keys(target.methods).join(', ')}`);
     }
 
-    return (await (program.methods as any)[methodName](...args).accounts(accounts).rpc()) as string;
+    return (await (target.methods as any)[methodName](...args).accounts(accounts).rpc()) as string;
   };
 
   const withErrorDetails = async (label: string, fn: () => Promise<void>) => {
@@ -187,16 +202,46 @@ export default function AuctionPage() {
     if (!requireCore() || !auctionIdBn) return;
     setStatus({ message: 'Creating auction...' });
     await withErrorDetails('create_auction', async () => {
-      const tx = await invoke(
-        'create_auction',
-        [auctionIdBn, new BN(minBid), new BN(minIncrement), new BN(duration)],
-        {
-          auctionHouse: auctionHouse!,
-          vault: vault!,
-          authority: authority!,
-          systemProgram: SystemProgram.programId,
-        }
-      );
+      const params = {
+        auctionId: auctionIdBn,
+        minBid: new BN(minBid),
+        minIncrement: new BN(minIncrement),
+        startTime: new BN(0),
+        duration: new BN(duration),
+        revealDuration: new BN(revealDuration),
+        auctionType: { firstPrice: {} },
+        tieBreak: { earliestCommit: {} },
+        unitCount: 0,
+        startPrice: new BN(0),
+        priceDecrement: new BN(0),
+        decayInterval: new BN(0),
+        buyNowPrice: new BN(0),
+        withdrawalPolicy: { forbidden: {} },
+        withdrawalPenaltyBps: 0,
+        itemAmount: new BN(0),
+        reserveCommitment: Array(32).fill(0),
+        extensionWindow: new BN(0),
+        extensionDuration: new BN(0),
+        maxExtension: new BN(0),
+        erValidator: ER_VALIDATOR,
+        commitFrequencyMs: 0,
+        proceedsSplits: [],
+      };
+      const tx = await invoke('create_auction', [params], {
+        config: configPda,
+        auctionHouse: auctionHouse!,
+        vault: vault!,
+        paymentMint: null,
+        vaultTokenAccount: null,
+        itemMint: null,
+        itemVault: null,
+        sellerItemAccount: null,
+        unitBook: null,
+        authority: authority!,
+        tokenProgram: null,
+        itemTokenProgram: null,
+        systemProgram: SystemProgram.programId,
+      });
       setStatus({ message: 'Auction created', tx });
     });
   };
@@ -215,30 +260,86 @@ export default function AuctionPage() {
     });
   };
 
-  const onSubmitSealedBid = async () => {
+  // The deposit is escrowed on L1 before any bid, and must cover the amount revealed later.
+  const onDepositToEscrow = async () => {
     if (!requireCore()) return;
-    setStatus({ message: 'Submitting sealed bid...' });
-    await withErrorDetails('submit_sealed_bid', async () => {
-      const tx = await invoke('submit_sealed_bid', [new BN(bidAmount)], {
+    setStatus({ message: 'Depositing to escrow...' });
+    await withErrorDetails('deposit_to_escrow', async () => {
+      const tx = await invoke('deposit_to_escrow', [new BN(bidAmount)], {
         auctionHouse: auctionHouse!,
         sealedBid: sealedBid!,
         vault: vault!,
+        paymentMint: null,
+        vaultTokenAccount: null,
+        bidderTokenAccount: null,
+        tokenProgram: null,
         bidder: bidder!,
         systemProgram: SystemProgram.programId,
       });
-      setStatus({ message: 'Bid submitted', tx });
+      setStatus({ message: 'Deposit escrowed', tx });
+    });
+  };
+
+  // Only the commitment goes on chain; the amount and salt stay in the browser until the reveal.
+  const onSubmitSealedBid = async () => {
+    if (!requireCore()) return;
+    setStatus({ message: 'Submitting sealed bid...' });
+    await withErrorDetails('submit_sealed_bid', async () => {
+      const salt = crypto.getRandomValues(new Uint8Array(32));
+      const commitment = await bidCommitment(new BN(bidAmount), salt, bidder!);
+      const tx = await invoke(
+        'submit_sealed_bid',
+        [commitment],
+        {
+          auctionHouse: auctionHouse!,
+          sealedBid: sealedBid!,
+          session: null,
+          signer: bidder!,
+        },
+        flowMode === 'per' ? erProgram : program
+      );
+      localStorage.setItem(
+        saltKey(sealedBid!),
+        JSON.stringify({ amount: bidAmount, salt: Buffer.from(salt).toString('hex') })
+      );
+      setStatus({ message: 'Bid submitted. Keep this browser until you reveal.', tx });
     });
   };
 
   const onDelegateBid = async () => {
-    if (!requireCore() || !bufferSealedBid || !delegationRecordSealedBid || !delegationMetadataSealedBid) return;
+    if (!requireCore() || !permission) return;
     setStatus({ message: 'Delegating bid to PER...' });
     await withErrorDetails('delegate_bid', async () => {
+      await invoke('create_bid_permission', [], {
+        auctionHouse: auctionHouse!,
+        sealedBid: sealedBid!,
+        permission,
+        bidder: bidder!,
+      });
+      await invoke('delegate_bid_permission', [], {
+        auctionHouse: auctionHouse!,
+        sealedBid: sealedBid!,
+        permission,
+        bufferPermission: pda([Buffer.from('buffer'), permission.toBuffer()], PERMISSION_PROGRAM_ID),
+        delegationRecordPermission: pda([Buffer.from('delegation'), permission.toBuffer()], DELEGATION_PROGRAM_ID),
+        delegationMetadataPermission: pda(
+          [Buffer.from('delegation-metadata'), permission.toBuffer()],
+          DELEGATION_PROGRAM_ID
+        ),
+        validator: ER_VALIDATOR,
+        bidder: bidder!,
+      });
       const tx = await invoke('delegate_bid', [], {
-        bufferSealedBid,
-        delegationRecordSealedBid,
-        delegationMetadataSealedBid,
+        auctionHouse: auctionHouse!,
+        bufferSealedBid: pda([Buffer.from('buffer'), sealedBid!.toBuffer()], EPHEMERAL_ROLLUPS_PROGRAM_ID),
+        delegationRecordSealedBid: pda([Buffer.from('delegation'), sealedBid!.toBuffer()], DELEGATION_PROGRAM_ID),
+        delegationMetadataSealedBid: pda(
+          [Buffer.from('delegation-metadata'), sealedBid!.toBuffer()],
+          DELEGATION_PROGRAM_ID
+        ),
         sealedBid: sealedBid!,
+        permission,
+        validator: ER_VALIDATOR,
         bidder: bidder!,
         ownerProgram: PROGRAM_ID,
         delegationProgram: DELEGATION_PROGRAM_ID,
@@ -248,41 +349,56 @@ export default function AuctionPage() {
     });
   };
 
-  const onProcessUndelegation = async () => {
-    if (!requireCore() || !bufferSealedBid) return;
-    setStatus({ message: 'Processing undelegation...' });
-    await withErrorDetails('process_undelegation', async () => {
-      const accountSeeds = [Buffer.from('bid'), auctionHouse!.toBuffer(), bidder!.toBuffer()];
-      const tx = await invoke('process_undelegation', [accountSeeds], {
-        baseAccount: sealedBid!,
-        buffer: bufferSealedBid,
-        payer: bidder!,
-        systemProgram: SystemProgram.programId,
-      });
-      setStatus({ message: 'Undelegation processed. You can continue L1 steps.', tx });
+  // Commits the bid from the rollup and hands it back to L1 for the reveal.
+  const onCommitAndUndelegateBid = async () => {
+    if (!requireCore()) return;
+    setStatus({ message: 'Committing and undelegating bid...' });
+    await withErrorDetails('commit_and_undelegate_bid', async () => {
+      const tx = await invoke(
+        'commit_and_undelegate_bid',
+        [],
+        {
+          auctionHouse: auctionHouse!,
+          sealedBid: sealedBid!,
+          payer: bidder!,
+          magicProgram: MAGIC_PROGRAM_ID,
+          magicContext: MAGIC_CONTEXT_ID,
+        },
+        erProgram
+      );
+      setStatus({ message: 'Bid committed and returned to L1', tx });
     });
   };
 
   const onCommitBid = async () => {
     if (!requireCore()) return;
     setStatus({ message: 'Committing bid...' });
-    await withErrorDetails('commit_bid', async () => {
-      const methodNames = Object.keys(program!.methods);
-      const useL1 = methodNames.includes('commit_bid_l1') || methodNames.includes('commitBidL1');
-      const tx = useL1
-        ? await invoke('commit_bid_l1', [], {
-            auctionHouse: auctionHouse!,
-            sealedBid: sealedBid!,
-            bidder: bidder!,
-          })
-        : await invoke('commit_bid', [], {
-            auctionHouse: auctionHouse!,
-            sealedBid: sealedBid!,
-            bidder: bidder!,
-            magicProgram: MAGIC_PROGRAM_ID,
-            magicContext: MAGIC_CONTEXT_ID,
-          });
-      setStatus({ message: useL1 ? 'Bid committed (L1 path)' : 'Bid committed', tx });
+    await withErrorDetails('commit_bid_l1', async () => {
+      const tx = await invoke('commit_bid_l1', [], {
+        auctionHouse: auctionHouse!,
+        sealedBid: sealedBid!,
+        bidder: bidder!,
+      });
+      setStatus({ message: 'Bid committed (L1 path)', tx });
+    });
+  };
+
+  const onRevealBid = async () => {
+    if (!requireCore()) return;
+    const stored = localStorage.getItem(saltKey(sealedBid!));
+    if (!stored) {
+      setStatus({ message: 'No sealed bid from this browser to reveal.' });
+      return;
+    }
+    setStatus({ message: 'Revealing bid...' });
+    await withErrorDetails('reveal_bid', async () => {
+      const { amount, salt } = JSON.parse(stored) as { amount: string; salt: string };
+      const tx = await invoke('reveal_bid', [new BN(amount), Array.from(Buffer.from(salt, 'hex'))], {
+        auctionHouse: auctionHouse!,
+        sealedBid: sealedBid!,
+        bidder: bidder!,
+      });
+      setStatus({ message: 'Bid revealed', tx });
     });
   };
 
@@ -293,6 +409,7 @@ export default function AuctionPage() {
       const tx = await invoke('settle_committed_bid', [], {
         auctionHouse,
         sealedBid: settleSealedBid,
+        unitBook: null,
       });
       setStatus({ message: 'Committed bid settled', tx });
     });
@@ -304,6 +421,7 @@ export default function AuctionPage() {
     await withErrorDetails('finalize_auction', async () => {
       const tx = await invoke('finalize_auction', [], {
         auctionHouse: auctionHouse!,
+        unitBook: null,
         authority: authority!,
       });
       setStatus({ message: 'Auction finalized', tx });
@@ -314,9 +432,18 @@ export default function AuctionPage() {
     if (!requireCore()) return;
     setStatus({ message: 'Claiming seller proceeds...' });
     await withErrorDetails('claim_seller_proceeds', async () => {
+      // A protocol fee is only taken when the config exists; it goes to its recipient.
+      const config = await program!.account.protocolConfig.fetchNullable(configPda);
       const tx = await invoke('claim_seller_proceeds', [], {
+        config: config ? configPda : null,
+        feeRecipient: config ? config.feeRecipient : null,
+        feeRecipientTokenAccount: null,
         auctionHouse: auctionHouse!,
         vault: vault!,
+        paymentMint: null,
+        vaultTokenAccount: null,
+        authorityTokenAccount: null,
+        tokenProgram: null,
         authority: authority!,
       });
       setStatus({ message: 'Seller proceeds claimed', tx });
@@ -331,6 +458,10 @@ export default function AuctionPage() {
         auctionHouse: auctionHouse!,
         sealedBid: sealedBid!,
         vault: vault!,
+        paymentMint: null,
+        vaultTokenAccount: null,
+        bidderTokenAccount: null,
+        tokenProgram: null,
         bidder: bidder!,
       });
       setStatus({ message: 'Refund claimed', tx });
@@ -356,8 +487,9 @@ export default function AuctionPage() {
         <div className="rounded-3xl border border-cyan-300/20 bg-slate-900/70 p-6">
           <h1 className="text-3xl font-black tracking-tight text-cyan-300 md:text-4xl">EphemeralBid User Flow</h1>
           <p className="mt-2 text-sm text-slate-200">
-            Seller creates auction. Bidder initializes account, places bid, commits. After end time, settle and
-            finalize. Seller claims proceeds, losers claim refund.
+            Seller creates auction. Bidder initializes account, escrows a deposit, submits a sealed commitment and
+            commits it. After end time, bidders reveal and settle; after the reveal window the seller finalizes and
+            claims proceeds, losers claim refund.
           </p>
           <div className="mt-4 flex flex-wrap items-center gap-3">
             <WalletMultiButton className="!bg-cyan-600 hover:!bg-cyan-700" />
@@ -407,6 +539,14 @@ export default function AuctionPage() {
               onChange={(e) => setDuration(e.target.value)}
             />
           </label>
+          <label className="text-sm text-slate-200">
+            Reveal Duration (seconds)
+            <input
+              className="mt-1 w-full rounded-lg bg-slate-800 p-2 text-white"
+              value={revealDuration}
+              onChange={(e) => setRevealDuration(e.target.value)}
+            />
+          </label>
           <label className="text-sm text-slate-200">
             Min Bid (lamports)
             <input
@@ -424,14 +564,14 @@ export default function AuctionPage() {
             />
           </label>
           <label className="text-sm text-slate-200">
-            Bid Amount (lamports)
+            Bid / Deposit Amount (lamports)
             <input
               className="mt-1 w-full rounded-lg bg-slate-800 p-2 text-white"
               value={bidAmount}
               onChange={(e) => setBidAmount(e.target.value)}
             />
           </label>
-          <label className="text-sm text-slate-200 md:col-span-3">
+          <label className="text-sm text-slate-200 md:col-span-2">
             Settle Bidder Pubkey (optional)
             <input
               className="mt-1 w-full rounded-lg bg-slate-800 p-2 text-white"
@@ -450,13 +590,13 @@ export default function AuctionPage() {
                 1. Create Auction
               </button>
               <button onClick={onFinalizeAuction} className="rounded-lg bg-amber-600 px-3 py-2 text-sm font-semibold">
-                6. Finalize Auction
+                9. Finalize Auction
               </button>
               <button
                 onClick={onClaimSellerProceeds}
                 className="rounded-lg bg-teal-600 px-3 py-2 text-sm font-semibold"
               >
-                7. Claim Seller Proceeds
+                10. Claim Seller Proceeds
               </button>
             </div>
           </div>
@@ -470,12 +610,15 @@ export default function AuctionPage() {
               >
                 2. Init Sealed Bid
               </button>
+              <button onClick={onDepositToEscrow} className="rounded-lg bg-sky-600 px-3 py-2 text-sm font-semibold">
+                3. Deposit to Escrow
+              </button>
               {flowMode === 'l1' && (
                 <button
                   onClick={onSubmitSealedBid}
                   className="rounded-lg bg-cyan-600 px-3 py-2 text-sm font-semibold"
                 >
-                  3. Submit Sealed Bid
+                  4. Submit Sealed Bid
                 </button>
               )}
               {flowMode === 'l1' && (
@@ -483,7 +626,7 @@ export default function AuctionPage() {
                   onClick={onCommitBid}
                   className="rounded-lg bg-violet-600 px-3 py-2 text-sm font-semibold"
                 >
-                  4. Commit Bid (L1)
+                  5. Commit Bid (L1)
                 </button>
               )}
               {flowMode === 'per' && (
@@ -491,15 +634,7 @@ export default function AuctionPage() {
                   onClick={onDelegateBid}
                   className="rounded-lg bg-indigo-600 px-3 py-2 text-sm font-semibold"
                 >
-                  3. Delegate Bid (PER)
-                </button>
-              )}
-              {flowMode === 'per' && (
-                <button
-                  onClick={onProcessUndelegation}
-                  className="rounded-lg bg-fuchsia-600 px-3 py-2 text-sm font-semibold"
-                >
-                  4. Process Undelegation
+                  4. Delegate Bid (PER)
                 </button>
               )}
               {flowMode === 'per' && (
@@ -507,15 +642,15 @@ export default function AuctionPage() {
                   onClick={onSubmitSealedBid}
                   className="rounded-lg bg-cyan-600 px-3 py-2 text-sm font-semibold"
                 >
-                  5. Submit Sealed Bid (after undelegation)
+                  5. Submit Sealed Bid (PER)
                 </button>
               )}
               {flowMode === 'per' && (
                 <button
-                  onClick={onCommitBid}
+                  onClick={onCommitAndUndelegateBid}
                   className="rounded-lg bg-violet-600 px-3 py-2 text-sm font-semibold"
                 >
-                  6. Commit Bid (L1)
+                  6. Commit &amp; Undelegate Bid
                 </button>
               )}
             </div>
@@ -524,17 +659,20 @@ export default function AuctionPage() {
           <div className="rounded-2xl border border-orange-300/20 bg-orange-950/20 p-4">
             <h2 className="text-sm font-bold text-orange-300">Settlement</h2>
             <div className="mt-3 grid gap-2">
+              <button onClick={onRevealBid} className="rounded-lg bg-amber-600 px-3 py-2 text-sm font-semibold">
+                7. Reveal Bid
+              </button>
               <button
                 onClick={onSettleCommittedBid}
                 className="rounded-lg bg-fuchsia-600 px-3 py-2 text-sm font-semibold"
               >
-                5. Settle Committed Bid
+                8. Settle Committed Bid
               </button>
               <button onClick={onClaimRefund} className="rounded-lg bg-orange-600 px-3 py-2 text-sm font-semibold">
-                8. Claim Refund (loser)
+                11. Claim Refund (loser)
               </button>
               <button onClick={onCloseSealedBid} className="rounded-lg bg-rose-600 px-3 py-2 text-sm font-semibold">
-                9. Close Sealed Bid
+                12. Close Sealed Bid
               </button>
             </div>
           </div>
@@ -13,20 +13,21 @@ export const IDL =
   },
   "instructions": [
     {
-      "name": "claim_refund",
+      "name": "accept_dutch_price",
       "discriminator": [
-        15,
-        16,
-        30,
-        161,
-        255,
-        228,
-        97,
-        60
+        153,
+        251,
+        109,
+        24,
+        66,
+        40,
+        45,
+        242
       ],
       "accounts": [
         {
-          "name": "auction_house"
+          "name": "auction_house",
+          "writable": true
         },
         {
           "name": "sealed_bid",
@@ -75,106 +76,78 @@ export const IDL =
           }
         },
         {
-          "name": "bidder",
-          "writable": true,
-          "signer": true,
-          "relations": [
-            "sealed_bid"
-          ]
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "claim_seller_proceeds",
-      "discriminator": [
-        118,
-        14,
-        102,
-        210,
-        141,
-        123,
-        88,
-        23
-      ],
-      "accounts": [
+          "name": "payment_mint",
+          "optional": true
+        },
         {
-          "name": "auction_house",
+          "name": "vault_token_account",
           "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
+                  118,
                   97,
                   117,
-                  99,
+                  108,
+                  116,
+                  95,
                   116,
-                  105,
                   111,
+                  107,
+                  101,
                   110
                 ]
               },
               {
                 "kind": "account",
-                "path": "authority"
-              },
-              {
-                "kind": "account",
-                "path": "auction_house.auction_id",
-                "account": "AuctionHouse"
+                "path": "auction_house"
               }
             ]
           }
         },
         {
-          "name": "vault",
+          "name": "bidder_token_account",
           "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  118,
-                  97,
-                  117,
-                  108,
-                  116
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "auction_house"
-              }
-            ]
-          }
+          "optional": true
         },
         {
-          "name": "authority",
+          "name": "token_program",
+          "optional": true
+        },
+        {
+          "name": "bidder",
           "writable": true,
           "signer": true,
           "relations": [
-            "auction_house"
+            "sealed_bid"
           ]
+        },
+        {
+          "name": "system_program",
+          "address": "11111111111111111111111111111111"
         }
       ],
       "args": []
     },
     {
-      "name": "close_sealed_bid",
+      "name": "buy_now",
       "discriminator": [
-        225,
-        18,
-        243,
-        66,
-        94,
-        161,
-        43,
-        127
+        242,
+        42,
+        184,
+        77,
+        133,
+        152,
+        118,
+        204
       ],
       "accounts": [
         {
-          "name": "auction_house"
+          "name": "auction_house",
+          "writable": true
         },
         {
           "name": "sealed_bid",
@@ -201,176 +174,190 @@ export const IDL =
           }
         },
         {
-          "name": "bidder",
+          "name": "vault",
           "writable": true,
-          "signer": true,
-          "relations": [
-            "sealed_bid"
-          ]
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "commit_bid",
-      "discriminator": [
-        149,
-        237,
-        198,
-        113,
-        53,
-        66,
-        70,
-        76
-      ],
-      "accounts": [
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
         {
-          "name": "auction_house",
-          "writable": true
+          "name": "payment_mint",
+          "optional": true
         },
         {
-          "name": "sealed_bid",
+          "name": "vault_token_account",
           "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  98,
-                  105,
-                  100
+                  118,
+                  97,
+                  117,
+                  108,
+                  116,
+                  95,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
                 ]
               },
               {
                 "kind": "account",
                 "path": "auction_house"
-              },
-              {
-                "kind": "account",
-                "path": "bidder"
               }
             ]
           }
         },
+        {
+          "name": "bidder_token_account",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "token_program",
+          "optional": true
+        },
         {
           "name": "bidder",
+          "writable": true,
           "signer": true,
           "relations": [
             "sealed_bid"
           ]
         },
         {
-          "name": "magic_program",
-          "address": "Magic11111111111111111111111111111111111111"
-        },
-        {
-          "name": "magic_context",
-          "writable": true,
-          "address": "MagicContext1111111111111111111111111111111"
+          "name": "system_program",
+          "address": "11111111111111111111111111111111"
         }
       ],
       "args": []
     },
     {
-      "name": "commit_bid_l1",
+      "name": "cancel_auction",
       "discriminator": [
-        199,
-        121,
-        188,
-        7,
-        79,
-        141,
-        176,
-        244
+        156,
+        43,
+        197,
+        110,
+        218,
+        105,
+        143,
+        182
       ],
       "accounts": [
         {
           "name": "auction_house",
-          "writable": true
-        },
-        {
-          "name": "sealed_bid",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  98,
+                  97,
+                  117,
+                  99,
+                  116,
                   105,
-                  100
+                  111,
+                  110
                 ]
               },
               {
                 "kind": "account",
-                "path": "auction_house"
+                "path": "authority"
               },
               {
                 "kind": "account",
-                "path": "bidder"
+                "path": "auction_house.auction_id",
+                "account": "AuctionHouse"
               }
             ]
           }
         },
         {
-          "name": "bidder",
+          "name": "authority",
           "signer": true,
           "relations": [
-            "sealed_bid"
+            "auction_house"
           ]
         }
       ],
       "args": []
     },
     {
-      "name": "create_auction",
+      "name": "claim_item",
       "discriminator": [
-        234,
-        6,
-        201,
-        246,
-        47,
-        219,
-        176,
-        107
+        32,
+        225,
+        122,
+        28,
+        30,
+        103,
+        205,
+        80
       ],
       "accounts": [
         {
           "name": "auction_house",
-          "writable": true,
+          "writable": true
+        },
+        {
+          "name": "vault",
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
+                  118,
                   97,
                   117,
-                  99,
-                  116,
-                  105,
-                  111,
-                  110
+                  108,
+                  116
                 ]
               },
               {
                 "kind": "account",
-                "path": "authority"
-              },
-              {
-                "kind": "arg",
-                "path": "auction_id"
+                "path": "auction_house"
               }
             ]
           }
         },
         {
-          "name": "vault",
+          "name": "item_mint"
+        },
+        {
+          "name": "item_vault",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
+                  105,
+                  116,
+                  101,
+                  109,
+                  95,
                   118,
                   97,
                   117,
@@ -386,49 +373,37 @@ export const IDL =
           }
         },
         {
-          "name": "authority",
-          "writable": true,
+          "name": "recipient_item_account",
+          "writable": true
+        },
+        {
+          "name": "claimer",
           "signer": true
         },
         {
-          "name": "system_program",
-          "address": "11111111111111111111111111111111"
+          "name": "item_token_program"
         }
       ],
-      "args": [
-        {
-          "name": "auction_id",
-          "type": "u64"
-        },
-        {
-          "name": "min_bid",
-          "type": "u64"
-        },
-        {
-          "name": "min_increment",
-          "type": "u64"
-        },
-        {
-          "name": "duration",
-          "type": "i64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "delegate_bid",
+      "name": "claim_refund",
       "discriminator": [
-        205,
-        246,
+        15,
+        16,
+        30,
+        161,
+        255,
+        228,
         97,
-        168,
-        93,
-        183,
-        203,
-        117
+        60
       ],
       "accounts": [
         {
-          "name": "buffer_sealed_bid",
+          "name": "auction_house"
+        },
+        {
+          "name": "sealed_bid",
           "writable": true,
           "pda": {
             "seeds": [
@@ -436,131 +411,84 @@ export const IDL =
                 "kind": "const",
                 "value": [
                   98,
-                  117,
-                  102,
-                  102,
-                  101,
-                  114
+                  105,
+                  100
                 ]
               },
               {
                 "kind": "account",
-                "path": "sealed_bid"
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "bidder"
               }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                242,
-                96,
-                133,
-                63,
-                142,
-                184,
-                10,
-                176,
-                73,
-                157,
-                225,
-                152,
-                140,
-                130,
-                123,
-                23,
-                164,
-                195,
-                182,
-                163,
-                62,
-                16,
-                255,
-                172,
-                208,
-                102,
-                49,
-                235,
-                221,
-                52,
-                15,
-                247
-              ]
-            }
+            ]
           }
         },
         {
-          "name": "delegation_record_sealed_bid",
+          "name": "vault",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  100,
-                  101,
-                  108,
-                  101,
-                  103,
+                  118,
                   97,
-                  116,
-                  105,
-                  111,
-                  110
+                  117,
+                  108,
+                  116
                 ]
               },
               {
                 "kind": "account",
-                "path": "sealed_bid"
+                "path": "auction_house"
               }
-            ],
-            "program": {
-              "kind": "account",
-              "path": "delegation_program"
-            }
+            ]
           }
         },
         {
-          "name": "delegation_metadata_sealed_bid",
+          "name": "payment_mint",
+          "optional": true
+        },
+        {
+          "name": "vault_token_account",
           "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  100,
-                  101,
-                  108,
-                  101,
-                  103,
+                  118,
                   97,
+                  117,
+                  108,
+                  116,
+                  95,
                   116,
-                  105,
                   111,
-                  110,
-                  45,
-                  109,
+                  107,
                   101,
-                  116,
-                  97,
-                  100,
-                  97,
-                  116,
-                  97
+                  110
                 ]
               },
               {
                 "kind": "account",
-                "path": "sealed_bid"
+                "path": "auction_house"
               }
-            ],
-            "program": {
-              "kind": "account",
-              "path": "delegation_program"
-            }
+            ]
           }
         },
         {
-          "name": "sealed_bid",
-          "writable": true
+          "name": "bidder_token_account",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "token_program",
+          "optional": true
         },
         {
           "name": "bidder",
@@ -569,35 +497,52 @@ export const IDL =
           "relations": [
             "sealed_bid"
           ]
-        },
-        {
-          "name": "owner_program",
-          "address": "HK92WjG3LE4JCWi5mU7pAvLz1JFBUwwh1v3obB9ajwnE"
-        },
-        {
-          "name": "delegation_program",
-          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
-        },
-        {
-          "name": "system_program",
-          "address": "11111111111111111111111111111111"
         }
       ],
       "args": []
     },
     {
-      "name": "finalize_auction",
+      "name": "claim_seller_proceeds",
       "discriminator": [
-        220,
-        209,
-        175,
-        193,
-        57,
-        132,
-        241,
-        168
+        118,
+        14,
+        102,
+        210,
+        141,
+        123,
+        88,
+        23
       ],
       "accounts": [
+        {
+          "name": "config",
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "fee_recipient",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "fee_recipient_token_account",
+          "writable": true,
+          "optional": true
+        },
         {
           "name": "auction_house",
           "writable": true,
@@ -628,145 +573,120 @@ export const IDL =
           }
         },
         {
-          "name": "authority",
-          "signer": true,
-          "relations": [
-            "auction_house"
-          ]
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "initialize_sealed_bid",
-      "discriminator": [
-        195,
-        245,
-        146,
-        115,
-        0,
-        245,
-        36,
-        107
-      ],
-      "accounts": [
-        {
-          "name": "auction_house",
-          "writable": true
-        },
-        {
-          "name": "sealed_bid",
+          "name": "vault",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  98,
-                  105,
-                  100
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
                 ]
               },
               {
                 "kind": "account",
                 "path": "auction_house"
-              },
-              {
-                "kind": "account",
-                "path": "bidder"
               }
             ]
           }
         },
         {
-          "name": "bidder",
-          "writable": true,
-          "signer": true
+          "name": "payment_mint",
+          "optional": true
         },
         {
-          "name": "system_program",
-          "address": "11111111111111111111111111111111"
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "process_undelegation",
-      "discriminator": [
-        196,
-        28,
-        41,
-        206,
-        48,
-        37,
-        51,
-        167
-      ],
-      "accounts": [
-        {
-          "name": "base_account",
-          "writable": true
-        },
-        {
-          "name": "buffer"
+          "name": "vault_token_account",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116,
+                  95,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
         },
         {
-          "name": "payer",
-          "writable": true
+          "name": "authority_token_account",
+          "writable": true,
+          "optional": true
         },
         {
-          "name": "system_program"
-        }
-      ],
-      "args": [
-        {
-          "name": "account_seeds",
-          "type": {
-            "vec": "bytes"
-          }
-        }
-      ]
-    },
-    {
-      "name": "settle_committed_bid",
-      "discriminator": [
-        82,
-        167,
-        20,
-        42,
-        105,
-        133,
-        247,
-        33
-      ],
-      "accounts": [
-        {
-          "name": "auction_house",
-          "writable": true
+          "name": "token_program",
+          "optional": true
         },
         {
-          "name": "sealed_bid",
-          "writable": true
+          "name": "authority",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "auction_house"
+          ]
         }
       ],
       "args": []
     },
     {
-      "name": "submit_sealed_bid",
+      "name": "claim_units",
       "discriminator": [
-        8,
-        44,
-        54,
-        67,
-        60,
+        175,
         62,
-        229,
-        117
+        188,
+        9,
+        102,
+        206,
+        248,
+        90
       ],
       "accounts": [
         {
-          "name": "auction_house",
-          "writable": true
+          "name": "auction_house"
+        },
+        {
+          "name": "unit_book",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  110,
+                  105,
+                  116,
+                  95,
+                  98,
+                  111,
+                  111,
+                  107
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
         },
         {
           "name": "sealed_bid",
@@ -814,6 +734,89 @@ export const IDL =
             ]
           }
         },
+        {
+          "name": "payment_mint",
+          "optional": true
+        },
+        {
+          "name": "vault_token_account",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116,
+                  95,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidder_token_account",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "token_program",
+          "optional": true
+        },
+        {
+          "name": "item_mint"
+        },
+        {
+          "name": "item_vault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  105,
+                  116,
+                  101,
+                  109,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "recipient_item_account",
+          "docs": [
+            "Only needed when the bidder won at least one unit."
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "item_token_program"
+        },
         {
           "name": "bidder",
           "writable": true,
@@ -821,92 +824,3126 @@ export const IDL =
           "relations": [
             "sealed_bid"
           ]
-        },
-        {
-          "name": "system_program",
-          "address": "11111111111111111111111111111111"
         }
       ],
-      "args": [
-        {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
-    }
-  ],
-  "accounts": [
-    {
-      "name": "AuctionHouse",
-      "discriminator": [
-        40,
-        108,
-        215,
-        107,
-        213,
-        85,
-        245,
-        48
-      ]
+      "args": []
     },
     {
-      "name": "SealedBid",
+      "name": "close_auction",
       "discriminator": [
-        199,
-        9,
-        212,
-        151,
+        225,
+        129,
+        91,
         48,
-        136,
-        163,
-        226
-      ]
-    }
-  ],
-  "events": [
-    {
-      "name": "AuctionCreated",
-      "discriminator": [
-        133,
-        190,
-        194,
-        65,
-        172,
-        0,
-        70,
-        178
-      ]
-    },
-    {
-      "name": "AuctionFinalized",
-      "discriminator": [
-        136,
-        160,
-        117,
-        237,
-        77,
-        211,
-        136,
-        28
-      ]
-    },
-    {
-      "name": "BidCommitted",
-      "discriminator": [
-        81,
-        13,
-        193,
-        139,
-        0,
-        168,
-        82,
-        55
-      ]
-    },
-    {
-      "name": "BidSettled",
-      "discriminator": [
-        234,
+        215,
+        73,
+        203,
+        172
+      ],
+      "accounts": [
+        {
+          "name": "auction_house",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  117,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              },
+              {
+                "kind": "account",
+                "path": "auction_house.auction_id",
+                "account": "AuctionHouse"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payment_mint",
+          "optional": true
+        },
+        {
+          "name": "vault_token_account",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116,
+                  95,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority_token_account",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "item_vault",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  105,
+                  116,
+                  101,
+                  109,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "auction_house"
+          ]
+        },
+        {
+          "name": "token_program",
+          "optional": true
+        },
+        {
+          "name": "item_token_program",
+          "optional": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "close_sealed_bid",
+      "discriminator": [
+        225,
+        18,
+        243,
+        66,
+        94,
+        161,
+        43,
+        127
+      ],
+      "accounts": [
+        {
+          "name": "auction_house",
+          "writable": true
+        },
+        {
+          "name": "sealed_bid",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "bidder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidder",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "sealed_bid"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "commit_and_undelegate_bid",
+      "discriminator": [
+        94,
+        12,
+        67,
+        51,
+        165,
+        79,
+        114,
+        145
+      ],
+      "accounts": [
+        {
+          "name": "auction_house"
+        },
+        {
+          "name": "sealed_bid",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "sealed_bid.bidder",
+                "account": "SealedBid"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "magic_program",
+          "address": "Magic11111111111111111111111111111111111111"
+        },
+        {
+          "name": "magic_context",
+          "writable": true,
+          "address": "MagicContext1111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "commit_auction",
+      "discriminator": [
+        52,
+        123,
+        63,
+        72,
+        73,
+        188,
+        66,
+        204
+      ],
+      "accounts": [
+        {
+          "name": "auction_house",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  117,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house.authority",
+                "account": "AuctionHouse"
+              },
+              {
+                "kind": "account",
+                "path": "auction_house.auction_id",
+                "account": "AuctionHouse"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "magic_program",
+          "address": "Magic11111111111111111111111111111111111111"
+        },
+        {
+          "name": "magic_context",
+          "writable": true,
+          "address": "MagicContext1111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "commit_bid",
+      "discriminator": [
+        149,
+        237,
+        198,
+        113,
+        53,
+        66,
+        70,
+        76
+      ],
+      "accounts": [
+        {
+          "name": "auction_house"
+        },
+        {
+          "name": "sealed_bid",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "sealed_bid.bidder",
+                "account": "SealedBid"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "sealed_bid.bidder",
+                "account": "SealedBid"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "docs": [
+            "The bidder, or the signer of their session key."
+          ],
+          "signer": true
+        },
+        {
+          "name": "magic_program",
+          "address": "Magic11111111111111111111111111111111111111"
+        },
+        {
+          "name": "magic_context",
+          "writable": true,
+          "address": "MagicContext1111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "commit_bid_l1",
+      "discriminator": [
+        199,
+        121,
+        188,
+        7,
+        79,
+        141,
+        176,
+        244
+      ],
+      "accounts": [
+        {
+          "name": "auction_house"
+        },
+        {
+          "name": "sealed_bid",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "bidder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidder",
+          "signer": true,
+          "relations": [
+            "sealed_bid"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "create_auction",
+      "discriminator": [
+        234,
+        6,
+        201,
+        246,
+        47,
+        219,
+        176,
+        107
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "auction_house",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  117,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              },
+              {
+                "kind": "arg",
+                "path": "params.auction_id"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payment_mint",
+          "optional": true
+        },
+        {
+          "name": "vault_token_account",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116,
+                  95,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "item_mint",
+          "optional": true
+        },
+        {
+          "name": "item_vault",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  105,
+                  116,
+                  101,
+                  109,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "seller_item_account",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "unit_book",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  110,
+                  105,
+                  116,
+                  95,
+                  98,
+                  111,
+                  111,
+                  107
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "token_program",
+          "optional": true
+        },
+        {
+          "name": "item_token_program",
+          "optional": true
+        },
+        {
+          "name": "system_program",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "params",
+          "type": {
+            "defined": {
+              "name": "CreateAuctionParams"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "create_bid_permission",
+      "discriminator": [
+        168,
+        176,
+        182,
+        33,
+        250,
+        139,
+        74,
+        43
+      ],
+      "accounts": [
+        {
+          "name": "auction_house"
+        },
+        {
+          "name": "sealed_bid",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "bidder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "permission",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  101,
+                  114,
+                  109,
+                  105,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110,
+                  58
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "sealed_bid"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "permission_program"
+            }
+          }
+        },
+        {
+          "name": "bidder",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "sealed_bid"
+          ]
+        },
+        {
+          "name": "permission_program",
+          "address": "ACLseoPoyC3cBqoUtkbjZ4aDrkurZW86v19pXz2XQnp1"
+        },
+        {
+          "name": "system_program",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "create_session",
+      "discriminator": [
+        242,
+        193,
+        143,
+        179,
+        150,
+        25,
+        122,
+        227
+      ],
+      "accounts": [
+        {
+          "name": "session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "bidder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidder",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "system_program",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "session_signer",
+          "type": "pubkey"
+        },
+        {
+          "name": "expires_at",
+          "type": "i64"
+        },
+        {
+          "name": "max_spend",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "delegate_auction",
+      "discriminator": [
+        85,
+        114,
+        125,
+        69,
+        80,
+        147,
+        146,
+        83
+      ],
+      "accounts": [
+        {
+          "name": "buffer_auction_house",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                242,
+                96,
+                133,
+                63,
+                142,
+                184,
+                10,
+                176,
+                73,
+                157,
+                225,
+                152,
+                140,
+                130,
+                123,
+                23,
+                164,
+                195,
+                182,
+                163,
+                62,
+                16,
+                255,
+                172,
+                208,
+                102,
+                49,
+                235,
+                221,
+                52,
+                15,
+                247
+              ]
+            }
+          }
+        },
+        {
+          "name": "delegation_record_auction_house",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegation_program"
+            }
+          }
+        },
+        {
+          "name": "delegation_metadata_auction_house",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegation_program"
+            }
+          }
+        },
+        {
+          "name": "auction_house",
+          "writable": true
+        },
+        {
+          "name": "validator"
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "auction_house"
+          ]
+        },
+        {
+          "name": "owner_program",
+          "address": "HK92WjG3LE4JCWi5mU7pAvLz1JFBUwwh1v3obB9ajwnE"
+        },
+        {
+          "name": "delegation_program",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+        },
+        {
+          "name": "system_program",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "delegate_bid",
+      "discriminator": [
+        205,
+        246,
+        97,
+        168,
+        93,
+        183,
+        203,
+        117
+      ],
+      "accounts": [
+        {
+          "name": "auction_house"
+        },
+        {
+          "name": "buffer_sealed_bid",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "sealed_bid"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                242,
+                96,
+                133,
+                63,
+                142,
+                184,
+                10,
+                176,
+                73,
+                157,
+                225,
+                152,
+                140,
+                130,
+                123,
+                23,
+                164,
+                195,
+                182,
+                163,
+                62,
+                16,
+                255,
+                172,
+                208,
+                102,
+                49,
+                235,
+                221,
+                52,
+                15,
+                247
+              ]
+            }
+          }
+        },
+        {
+          "name": "delegation_record_sealed_bid",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "sealed_bid"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegation_program"
+            }
+          }
+        },
+        {
+          "name": "delegation_metadata_sealed_bid",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "sealed_bid"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegation_program"
+            }
+          }
+        },
+        {
+          "name": "sealed_bid",
+          "writable": true
+        },
+        {
+          "name": "permission",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  101,
+                  114,
+                  109,
+                  105,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110,
+                  58
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "sealed_bid"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                136,
+                161,
+                10,
+                196,
+                33,
+                152,
+                1,
+                214,
+                246,
+                106,
+                29,
+                60,
+                6,
+                152,
+                192,
+                102,
+                169,
+                175,
+                212,
+                217,
+                180,
+                252,
+                231,
+                71,
+                151,
+                141,
+                209,
+                5,
+                168,
+                212,
+                103,
+                82
+              ]
+            }
+          }
+        },
+        {
+          "name": "validator"
+        },
+        {
+          "name": "bidder",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "sealed_bid"
+          ]
+        },
+        {
+          "name": "owner_program",
+          "address": "HK92WjG3LE4JCWi5mU7pAvLz1JFBUwwh1v3obB9ajwnE"
+        },
+        {
+          "name": "delegation_program",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+        },
+        {
+          "name": "system_program",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "delegate_bid_permission",
+      "discriminator": [
+        89,
+        148,
+        253,
+        195,
+        18,
+        35,
+        106,
+        153
+      ],
+      "accounts": [
+        {
+          "name": "auction_house"
+        },
+        {
+          "name": "sealed_bid",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "bidder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "permission",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  101,
+                  114,
+                  109,
+                  105,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110,
+                  58
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "sealed_bid"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "permission_program"
+            }
+          }
+        },
+        {
+          "name": "buffer_permission",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "permission"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "permission_program"
+            }
+          }
+        },
+        {
+          "name": "delegation_record_permission",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "permission"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegation_program"
+            }
+          }
+        },
+        {
+          "name": "delegation_metadata_permission",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "permission"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegation_program"
+            }
+          }
+        },
+        {
+          "name": "validator"
+        },
+        {
+          "name": "bidder",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "sealed_bid"
+          ]
+        },
+        {
+          "name": "permission_program",
+          "address": "ACLseoPoyC3cBqoUtkbjZ4aDrkurZW86v19pXz2XQnp1"
+        },
+        {
+          "name": "delegation_program",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+        },
+        {
+          "name": "system_program",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "delegate_session",
+      "discriminator": [
+        82,
+        83,
+        119,
+        119,
+        196,
+        219,
+        5,
+        197
+      ],
+      "accounts": [
+        {
+          "name": "buffer_session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "session"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                242,
+                96,
+                133,
+                63,
+                142,
+                184,
+                10,
+                176,
+                73,
+                157,
+                225,
+                152,
+                140,
+                130,
+                123,
+                23,
+                164,
+                195,
+                182,
+                163,
+                62,
+                16,
+                255,
+                172,
+                208,
+                102,
+                49,
+                235,
+                221,
+                52,
+                15,
+                247
+              ]
+            }
+          }
+        },
+        {
+          "name": "delegation_record_session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "session"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegation_program"
+            }
+          }
+        },
+        {
+          "name": "delegation_metadata_session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "session"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegation_program"
+            }
+          }
+        },
+        {
+          "name": "session",
+          "writable": true
+        },
+        {
+          "name": "validator"
+        },
+        {
+          "name": "bidder",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "session"
+          ]
+        },
+        {
+          "name": "owner_program",
+          "address": "HK92WjG3LE4JCWi5mU7pAvLz1JFBUwwh1v3obB9ajwnE"
+        },
+        {
+          "name": "delegation_program",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+        },
+        {
+          "name": "system_program",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "deposit_to_escrow",
+      "discriminator": [
+        246,
+        134,
+        57,
+        199,
+        116,
+        101,
+        68,
+        224
+      ],
+      "accounts": [
+        {
+          "name": "auction_house"
+        },
+        {
+          "name": "sealed_bid",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "bidder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payment_mint",
+          "optional": true
+        },
+        {
+          "name": "vault_token_account",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116,
+                  95,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidder_token_account",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "token_program",
+          "optional": true
+        },
+        {
+          "name": "bidder",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "sealed_bid"
+          ]
+        },
+        {
+          "name": "system_program",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "finalize_auction",
+      "discriminator": [
+        220,
+        209,
+        175,
+        193,
+        57,
+        132,
+        241,
+        168
+      ],
+      "accounts": [
+        {
+          "name": "auction_house",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  117,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              },
+              {
+                "kind": "account",
+                "path": "auction_house.auction_id",
+                "account": "AuctionHouse"
+              }
+            ]
+          }
+        },
+        {
+          "name": "unit_book",
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  110,
+                  105,
+                  116,
+                  95,
+                  98,
+                  111,
+                  111,
+                  107
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "auction_house"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initialize_config",
+      "discriminator": [
+        208,
+        127,
+        21,
+        1,
+        194,
+        190,
+        196,
+        70
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program",
+          "address": "HK92WjG3LE4JCWi5mU7pAvLz1JFBUwwh1v3obB9ajwnE"
+        },
+        {
+          "name": "program_data"
+        },
+        {
+          "name": "fee_recipient"
+        },
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "system_program",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "fee_bps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "initialize_sealed_bid",
+      "discriminator": [
+        195,
+        245,
+        146,
+        115,
+        0,
+        245,
+        36,
+        107
+      ],
+      "accounts": [
+        {
+          "name": "auction_house",
+          "writable": true
+        },
+        {
+          "name": "sealed_bid",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "bidder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidder",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "system_program",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "place_escrowed_bid",
+      "discriminator": [
+        52,
+        38,
+        185,
+        184,
+        171,
+        215,
+        133,
+        126
+      ],
+      "accounts": [
+        {
+          "name": "auction_house",
+          "writable": true
+        },
+        {
+          "name": "sealed_bid",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "bidder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidder",
+          "signer": true,
+          "relations": [
+            "sealed_bid"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "place_open_bid",
+      "discriminator": [
+        144,
+        6,
+        105,
+        172,
+        76,
+        188,
+        169,
+        249
+      ],
+      "accounts": [
+        {
+          "name": "auction_house",
+          "writable": true
+        },
+        {
+          "name": "sealed_bid",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "bidder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payment_mint",
+          "optional": true
+        },
+        {
+          "name": "vault_token_account",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116,
+                  95,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidder_token_account",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "token_program",
+          "optional": true
+        },
+        {
+          "name": "bidder",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "sealed_bid"
+          ]
+        },
+        {
+          "name": "system_program",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "process_undelegation",
+      "discriminator": [
+        196,
+        28,
+        41,
+        206,
+        48,
+        37,
+        51,
+        167
+      ],
+      "accounts": [
+        {
+          "name": "base_account",
+          "writable": true
+        },
+        {
+          "name": "buffer"
+        },
+        {
+          "name": "payer",
+          "writable": true
+        },
+        {
+          "name": "system_program"
+        }
+      ],
+      "args": [
+        {
+          "name": "account_seeds",
+          "type": {
+            "vec": "bytes"
+          }
+        }
+      ]
+    },
+    {
+      "name": "release_bid_permission",
+      "discriminator": [
+        125,
+        62,
+        232,
+        155,
+        78,
+        29,
+        1,
+        247
+      ],
+      "accounts": [
+        {
+          "name": "auction_house"
+        },
+        {
+          "name": "sealed_bid",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "sealed_bid.bidder",
+                "account": "SealedBid"
+              }
+            ]
+          }
+        },
+        {
+          "name": "permission",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  101,
+                  114,
+                  109,
+                  105,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110,
+                  58
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "sealed_bid"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "permission_program"
+            }
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "permission_program",
+          "address": "ACLseoPoyC3cBqoUtkbjZ4aDrkurZW86v19pXz2XQnp1"
+        },
+        {
+          "name": "magic_program",
+          "address": "Magic11111111111111111111111111111111111111"
+        },
+        {
+          "name": "magic_context",
+          "writable": true,
+          "address": "MagicContext1111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "reveal_bid",
+      "discriminator": [
+        48,
+        73,
+        28,
+        255,
+        202,
+        126,
+        236,
+        196
+      ],
+      "accounts": [
+        {
+          "name": "auction_house"
+        },
+        {
+          "name": "sealed_bid",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "bidder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidder",
+          "signer": true,
+          "relations": [
+            "sealed_bid"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "salt",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "reveal_reserve",
+      "discriminator": [
+        35,
+        70,
+        113,
+        147,
+        101,
+        240,
+        175,
+        78
+      ],
+      "accounts": [
+        {
+          "name": "auction_house",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  117,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              },
+              {
+                "kind": "account",
+                "path": "auction_house.auction_id",
+                "account": "AuctionHouse"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "auction_house"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "reserve_price",
+          "type": "u64"
+        },
+        {
+          "name": "salt",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "reveal_unit_bid",
+      "discriminator": [
+        140,
+        240,
+        140,
+        22,
+        181,
+        185,
+        24,
+        39
+      ],
+      "accounts": [
+        {
+          "name": "auction_house"
+        },
+        {
+          "name": "sealed_bid",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "bidder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidder",
+          "signer": true,
+          "relations": [
+            "sealed_bid"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "unit_price",
+          "type": "u64"
+        },
+        {
+          "name": "quantity",
+          "type": "u32"
+        },
+        {
+          "name": "salt",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "revoke_session",
+      "discriminator": [
+        86,
+        92,
+        198,
+        120,
+        144,
+        2,
+        7,
+        194
+      ],
+      "accounts": [
+        {
+          "name": "session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "bidder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidder",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "session"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "settle_committed_bid",
+      "discriminator": [
+        82,
+        167,
+        20,
+        42,
+        105,
+        133,
+        247,
+        33
+      ],
+      "accounts": [
+        {
+          "name": "auction_house",
+          "writable": true
+        },
+        {
+          "name": "sealed_bid",
+          "writable": true
+        },
+        {
+          "name": "unit_book",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  110,
+                  105,
+                  116,
+                  95,
+                  98,
+                  111,
+                  111,
+                  107
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "settle_committed_bids",
+      "discriminator": [
+        127,
+        221,
+        187,
+        18,
+        235,
+        169,
+        99,
+        153
+      ],
+      "accounts": [
+        {
+          "name": "auction_house",
+          "writable": true
+        },
+        {
+          "name": "unit_book",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  110,
+                  105,
+                  116,
+                  95,
+                  98,
+                  111,
+                  111,
+                  107
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "submit_sealed_bid",
+      "discriminator": [
+        8,
+        44,
+        54,
+        67,
+        60,
+        62,
+        229,
+        117
+      ],
+      "accounts": [
+        {
+          "name": "auction_house"
+        },
+        {
+          "name": "sealed_bid",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "sealed_bid.bidder",
+                "account": "SealedBid"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "sealed_bid.bidder",
+                "account": "SealedBid"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "docs": [
+            "The bidder, or the signer of their session key."
+          ],
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "commitment",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "undelegate_auction",
+      "discriminator": [
+        202,
+        135,
+        68,
+        196,
+        88,
+        202,
+        14,
+        69
+      ],
+      "accounts": [
+        {
+          "name": "auction_house",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  117,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house.authority",
+                "account": "AuctionHouse"
+              },
+              {
+                "kind": "account",
+                "path": "auction_house.auction_id",
+                "account": "AuctionHouse"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "magic_program",
+          "address": "Magic11111111111111111111111111111111111111"
+        },
+        {
+          "name": "magic_context",
+          "writable": true,
+          "address": "MagicContext1111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "undelegate_session",
+      "discriminator": [
+        110,
+        234,
+        80,
+        245,
+        77,
+        79,
+        58,
+        116
+      ],
+      "accounts": [
+        {
+          "name": "session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "bidder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidder",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "session"
+          ]
+        },
+        {
+          "name": "magic_program",
+          "address": "Magic11111111111111111111111111111111111111"
+        },
+        {
+          "name": "magic_context",
+          "writable": true,
+          "address": "MagicContext1111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "update_config",
+      "discriminator": [
+        29,
+        158,
+        252,
+        191,
+        10,
+        83,
+        219,
+        99
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "fee_recipient"
+        },
+        {
+          "name": "admin",
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "admin",
+          "type": "pubkey"
+        },
+        {
+          "name": "fee_bps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "withdraw_bid",
+      "discriminator": [
+        110,
+        53,
+        157,
+        195,
+        147,
+        100,
+        110,
+        73
+      ],
+      "accounts": [
+        {
+          "name": "auction_house",
+          "writable": true
+        },
+        {
+          "name": "sealed_bid",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              },
+              {
+                "kind": "account",
+                "path": "bidder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payment_mint",
+          "optional": true
+        },
+        {
+          "name": "vault_token_account",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116,
+                  95,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "auction_house"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidder_token_account",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "token_program",
+          "optional": true
+        },
+        {
+          "name": "bidder",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "sealed_bid"
+          ]
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "AuctionHouse",
+      "discriminator": [
+        40,
+        108,
+        215,
+        107,
+        213,
+        85,
+        245,
+        48
+      ]
+    },
+    {
+      "name": "ProtocolConfig",
+      "discriminator": [
+        207,
+        91,
+        250,
+        28,
+        152,
+        179,
+        215,
+        209
+      ]
+    },
+    {
+      "name": "SealedBid",
+      "discriminator": [
+        199,
+        9,
+        212,
+        151,
+        48,
+        136,
+        163,
+        226
+      ]
+    },
+    {
+      "name": "SessionKey",
+      "discriminator": [
+        93,
+        186,
+        163,
+        139,
+        160,
+        255,
+        81,
+        112
+      ]
+    },
+    {
+      "name": "UnitBook",
+      "discriminator": [
+        64,
+        179,
+        206,
+        9,
+        92,
+        121,
+        82,
+        10
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "AuctionCancelled",
+      "discriminator": [
+        22,
+        32,
+        51,
+        83,
+        215,
+        194,
+        171,
+        209
+      ]
+    },
+    {
+      "name": "AuctionClosed",
+      "discriminator": [
+        104,
+        72,
+        168,
+        177,
+        241,
+        79,
+        231,
+        167
+      ]
+    },
+    {
+      "name": "AuctionCreated",
+      "discriminator": [
+        133,
+        190,
+        194,
+        65,
+        172,
+        0,
+        70,
+        178
+      ]
+    },
+    {
+      "name": "AuctionExtended",
+      "discriminator": [
+        204,
+        229,
+        238,
+        200,
+        189,
+        21,
+        50,
+        41
+      ]
+    },
+    {
+      "name": "AuctionFinalized",
+      "discriminator": [
+        136,
+        160,
+        117,
+        237,
+        77,
+        211,
+        136,
+        28
+      ]
+    },
+    {
+      "name": "BidCommitted",
+      "discriminator": [
+        81,
+        13,
+        193,
+        139,
+        0,
+        168,
+        82,
+        55
+      ]
+    },
+    {
+      "name": "BidForfeited",
+      "discriminator": [
+        192,
+        152,
+        68,
+        119,
+        224,
+        241,
+        162,
+        253
+      ]
+    },
+    {
+      "name": "BidRevealed",
+      "discriminator": [
+        227,
+        144,
+        125,
+        229,
+        28,
+        109,
+        18,
+        209
+      ]
+    },
+    {
+      "name": "BidSettled",
+      "discriminator": [
+        234,
         32,
         141,
         114,
@@ -917,192 +3954,1609 @@ export const IDL =
       ]
     },
     {
-      "name": "BidSubmitted",
+      "name": "BidSubmitted",
+      "discriminator": [
+        116,
+        72,
+        108,
+        240,
+        175,
+        70,
+        56,
+        22
+      ]
+    },
+    {
+      "name": "BidWithdrawn",
+      "discriminator": [
+        145,
+        195,
+        97,
+        230,
+        166,
+        54,
+        74,
+        206
+      ]
+    },
+    {
+      "name": "BoughtNow",
+      "discriminator": [
+        251,
+        234,
+        170,
+        42,
+        220,
+        198,
+        86,
+        127
+      ]
+    },
+    {
+      "name": "DutchPriceAccepted",
+      "discriminator": [
+        2,
+        159,
+        195,
+        41,
+        5,
+        146,
+        117,
+        169
+      ]
+    },
+    {
+      "name": "EscrowDeposited",
+      "discriminator": [
+        28,
+        193,
+        105,
+        27,
+        40,
+        101,
+        65,
+        211
+      ]
+    },
+    {
+      "name": "ItemClaimed",
+      "discriminator": [
+        13,
+        6,
+        96,
+        131,
+        169,
+        206,
+        1,
+        184
+      ]
+    },
+    {
+      "name": "OpenBidPlaced",
+      "discriminator": [
+        15,
+        123,
+        177,
+        221,
+        120,
+        250,
+        48,
+        90
+      ]
+    },
+    {
+      "name": "ProceedsDistributed",
+      "discriminator": [
+        54,
+        131,
+        213,
+        126,
+        30,
+        160,
+        20,
+        164
+      ]
+    },
+    {
+      "name": "ProtocolConfigUpdated",
+      "discriminator": [
+        20,
+        99,
+        32,
+        237,
+        111,
+        86,
+        195,
+        199
+      ]
+    },
+    {
+      "name": "RefundClaimed",
+      "discriminator": [
+        136,
+        64,
+        242,
+        99,
+        4,
+        244,
+        208,
+        130
+      ]
+    },
+    {
+      "name": "ReserveRevealed",
+      "discriminator": [
+        231,
+        30,
+        8,
+        215,
+        173,
+        191,
+        233,
+        251
+      ]
+    },
+    {
+      "name": "SellerProceedsClaimed",
+      "discriminator": [
+        40,
+        165,
+        152,
+        185,
+        201,
+        108,
+        110,
+        60
+      ]
+    },
+    {
+      "name": "SessionCreated",
+      "discriminator": [
+        107,
+        111,
+        254,
+        25,
+        21,
+        122,
+        220,
+        225
+      ]
+    },
+    {
+      "name": "SessionRevoked",
+      "discriminator": [
+        90,
+        48,
+        35,
+        234,
+        203,
+        192,
+        126,
+        211
+      ]
+    },
+    {
+      "name": "UnitsClaimed",
       "discriminator": [
-        116,
-        72,
-        108,
-        240,
-        175,
+        152,
+        123,
+        169,
+        198,
+        73,
+        40,
         70,
-        56,
-        22
+        33
       ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "AuctionActive",
+      "msg": "Auction is still active"
     },
     {
-      "name": "RefundClaimed",
-      "discriminator": [
-        136,
-        64,
-        242,
-        99,
-        4,
-        244,
-        208,
-        130
-      ]
+      "code": 6001,
+      "name": "AuctionEnded",
+      "msg": "Auction has ended"
+    },
+    {
+      "code": 6002,
+      "name": "AuctionFinalized",
+      "msg": "Auction already finalized"
+    },
+    {
+      "code": 6003,
+      "name": "AuctionNotFinalized",
+      "msg": "Auction not finalized"
+    },
+    {
+      "code": 6004,
+      "name": "BidIncrementTooSmall",
+      "msg": "Bid increment is too small"
+    },
+    {
+      "code": 6005,
+      "name": "BidBelowMinimum",
+      "msg": "Bid is below auction minimum"
+    },
+    {
+      "code": 6006,
+      "name": "CannotDelegate",
+      "msg": "Cannot delegate this account in its current state"
+    },
+    {
+      "code": 6007,
+      "name": "AccountNotDelegated",
+      "msg": "Bid account is not delegated"
+    },
+    {
+      "code": 6008,
+      "name": "BidNotCommitted",
+      "msg": "Bid account is not committed"
+    },
+    {
+      "code": 6009,
+      "name": "BidAlreadySettled",
+      "msg": "Bid account has already been settled"
+    },
+    {
+      "code": 6010,
+      "name": "UnsettledCommittedBids",
+      "msg": "There are unsettled bids"
+    },
+    {
+      "code": 6011,
+      "name": "BidAuctionMismatch",
+      "msg": "Bid account is linked to a different auction"
+    },
+    {
+      "code": 6012,
+      "name": "InvalidDuration",
+      "msg": "Duration must be greater than zero"
+    },
+    {
+      "code": 6013,
+      "name": "InvalidMinBid",
+      "msg": "Minimum bid must be greater than zero"
+    },
+    {
+      "code": 6014,
+      "name": "MathOverflow",
+      "msg": "Integer overflow"
+    },
+    {
+      "code": 6015,
+      "name": "ProceedsAlreadyClaimed",
+      "msg": "Seller proceeds have already been claimed"
+    },
+    {
+      "code": 6016,
+      "name": "NoWinningBid",
+      "msg": "No winning bid in this auction"
+    },
+    {
+      "code": 6017,
+      "name": "WinnerNoRefund",
+      "msg": "Winner cannot claim refund"
+    },
+    {
+      "code": 6018,
+      "name": "RefundAlreadyClaimed",
+      "msg": "Refund already claimed"
+    },
+    {
+      "code": 6019,
+      "name": "NoRefundAvailable",
+      "msg": "No refundable amount available"
+    },
+    {
+      "code": 6020,
+      "name": "InsufficientVaultBalance",
+      "msg": "Vault balance is insufficient"
+    },
+    {
+      "code": 6021,
+      "name": "CloseNotAllowed",
+      "msg": "Bid account cannot be closed yet"
+    },
+    {
+      "code": 6022,
+      "name": "MissingCommitment",
+      "msg": "Bid commitment is missing"
+    },
+    {
+      "code": 6023,
+      "name": "InvalidReveal",
+      "msg": "Revealed bid does not match commitment"
+    },
+    {
+      "code": 6024,
+      "name": "BidAlreadyRevealed",
+      "msg": "Bid has already been revealed"
+    },
+    {
+      "code": 6025,
+      "name": "BidExceedsDeposit",
+      "msg": "Revealed bid exceeds escrowed deposit"
+    },
+    {
+      "code": 6026,
+      "name": "RevealWindowClosed",
+      "msg": "Reveal window has closed"
+    },
+    {
+      "code": 6027,
+      "name": "RevealWindowOpen",
+      "msg": "Reveal window is still open"
+    },
+    {
+      "code": 6028,
+      "name": "BidForfeited",
+      "msg": "Unrevealed bid deposit was forfeited"
+    },
+    {
+      "code": 6029,
+      "name": "MissingTokenAccounts",
+      "msg": "Token accounts are required for SPL-denominated auctions"
+    },
+    {
+      "code": 6030,
+      "name": "PaymentMintMismatch",
+      "msg": "Token account does not match auction payment mint"
+    },
+    {
+      "code": 6031,
+      "name": "MissingItemAccounts",
+      "msg": "Item escrow accounts are incomplete"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidItemAmount",
+      "msg": "Item amount must be greater than zero"
+    },
+    {
+      "code": 6033,
+      "name": "NoEscrowedItem",
+      "msg": "Auction has no escrowed item"
+    },
+    {
+      "code": 6034,
+      "name": "ItemAlreadyClaimed",
+      "msg": "Item has already been claimed"
+    },
+    {
+      "code": 6035,
+      "name": "ItemClaimNotAllowed",
+      "msg": "Signer is not entitled to the escrowed item"
+    },
+    {
+      "code": 6036,
+      "name": "NoReserve",
+      "msg": "Auction has no sealed reserve"
+    },
+    {
+      "code": 6037,
+      "name": "ReserveAlreadyRevealed",
+      "msg": "Reserve has already been revealed"
+    },
+    {
+      "code": 6038,
+      "name": "InvalidReserveReveal",
+      "msg": "Revealed reserve does not match commitment"
+    },
+    {
+      "code": 6039,
+      "name": "ReserveNotMet",
+      "msg": "Reserve price was not met"
+    },
+    {
+      "code": 6040,
+      "name": "AuctionCancelled",
+      "msg": "Auction has been cancelled"
+    },
+    {
+      "code": 6041,
+      "name": "CancelNotAllowed",
+      "msg": "Auction cannot be cancelled in its current state"
+    },
+    {
+      "code": 6042,
+      "name": "OutstandingBids",
+      "msg": "Bid accounts are still open for this auction"
+    },
+    {
+      "code": 6043,
+      "name": "ProceedsNotClaimed",
+      "msg": "Seller proceeds have not been claimed"
+    },
+    {
+      "code": 6044,
+      "name": "ItemNotClaimed",
+      "msg": "Escrowed item has not been claimed"
+    },
+    {
+      "code": 6045,
+      "name": "InvalidExtension",
+      "msg": "Soft-close extension settings are invalid"
+    },
+    {
+      "code": 6046,
+      "name": "AuctionNotStarted",
+      "msg": "Auction has not started yet"
+    },
+    {
+      "code": 6047,
+      "name": "InvalidStartTime",
+      "msg": "Start time cannot be in the past"
+    },
+    {
+      "code": 6048,
+      "name": "InvalidBidAccount",
+      "msg": "Account is not a sealed bid PDA for this auction"
+    },
+    {
+      "code": 6049,
+      "name": "InvalidBatchSize",
+      "msg": "Settlement batch is empty or too large"
+    },
+    {
+      "code": 6050,
+      "name": "UnsupportedAuctionType",
+      "msg": "Instruction is not supported for this auction type"
+    },
+    {
+      "code": 6051,
+      "name": "InvalidDutchSchedule",
+      "msg": "Dutch price schedule is invalid"
+    },
+    {
+      "code": 6052,
+      "name": "InsufficientDeposit",
+      "msg": "Escrowed deposit does not cover the price"
+    },
+    {
+      "code": 6053,
+      "name": "LeaderCannotWithdraw",
+      "msg": "Current leader cannot withdraw their deposit"
+    },
+    {
+      "code": 6054,
+      "name": "InvalidUnitCount",
+      "msg": "Unit count is invalid for this auction"
+    },
+    {
+      "code": 6055,
+      "name": "MissingUnitBook",
+      "msg": "Multi-unit auctions require their unit book account"
+    },
+    {
+      "code": 6056,
+      "name": "InvalidQuantity",
+      "msg": "Bid quantity is invalid"
+    },
+    {
+      "code": 6057,
+      "name": "InvalidBuyNowPrice",
+      "msg": "Buy-it-now price is invalid for this auction"
+    },
+    {
+      "code": 6058,
+      "name": "BuyNowUnavailable",
+      "msg": "Buy-it-now is not available for this auction"
+    },
+    {
+      "code": 6059,
+      "name": "AuctionBoughtOut",
+      "msg": "Auction was ended by a buy-it-now purchase"
+    },
+    {
+      "code": 6060,
+      "name": "InvalidWithdrawalPolicy",
+      "msg": "Withdrawal policy settings are invalid"
+    },
+    {
+      "code": 6061,
+      "name": "WithdrawalNotAllowed",
+      "msg": "This auction does not allow bid withdrawal"
+    },
+    {
+      "code": 6062,
+      "name": "Unauthorized",
+      "msg": "Signer is not authorized for this action"
+    },
+    {
+      "code": 6063,
+      "name": "InvalidFeeBps",
+      "msg": "Fee basis points exceed 100%"
+    },
+    {
+      "code": 6064,
+      "name": "FeeRecipientMismatch",
+      "msg": "Fee recipient account does not match protocol config"
+    },
+    {
+      "code": 6065,
+      "name": "InvalidProceedsSplits",
+      "msg": "Proceeds splits are invalid"
+    },
+    {
+      "code": 6066,
+      "name": "SplitRecipientMismatch",
+      "msg": "Split recipient account is missing or does not match"
+    },
+    {
+      "code": 6067,
+      "name": "ValidatorMismatch",
+      "msg": "Validator does not match the auction's ER validator"
+    },
+    {
+      "code": 6068,
+      "name": "InvalidSession",
+      "msg": "Session key settings are invalid"
+    },
+    {
+      "code": 6069,
+      "name": "SessionExpired",
+      "msg": "Session key has expired"
+    },
+    {
+      "code": 6070,
+      "name": "SessionSpendExceeded",
+      "msg": "Escrowed deposit exceeds the session's spend limit"
+    },
+    {
+      "code": 6071,
+      "name": "MissingFeeAccounts",
+      "msg": "Protocol config and fee recipient are required to pay the fee"
+    },
+    {
+      "code": 6072,
+      "name": "AuctionDelegated",
+      "msg": "Auction is delegated; register and fund bids before delegating it"
+    },
+    {
+      "code": 6073,
+      "name": "FeeRecipientNotRentExempt",
+      "msg": "Fee recipient must be rent exempt"
+    }
+  ],
+  "types": [
+    {
+      "name": "AuctionCancelled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "bidder_count",
+            "type": "u32"
+          }
+        ]
+      }
     },
     {
-      "name": "SellerProceedsClaimed",
-      "discriminator": [
-        40,
-        165,
-        152,
-        185,
-        201,
-        108,
-        110,
-        60
-      ]
-    }
-  ],
-  "errors": [
+      "name": "AuctionClosed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "swept_lamports",
+            "type": "u64"
+          },
+          {
+            "name": "swept_tokens",
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
-      "code": 6000,
-      "name": "AuctionActive",
-      "msg": "Auction is still active"
+      "name": "AuctionCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "auction_id",
+            "type": "u64"
+          },
+          {
+            "name": "auction_type",
+            "type": {
+              "defined": {
+                "name": "AuctionType"
+              }
+            }
+          },
+          {
+            "name": "payment_mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "item_mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "item_amount",
+            "type": "u64"
+          },
+          {
+            "name": "has_reserve",
+            "type": "bool"
+          },
+          {
+            "name": "min_bid",
+            "type": "u64"
+          },
+          {
+            "name": "min_increment",
+            "type": "u64"
+          },
+          {
+            "name": "starts_at",
+            "type": "i64"
+          },
+          {
+            "name": "ends_at",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6001,
-      "name": "AuctionEnded",
-      "msg": "Auction has ended"
+      "name": "AuctionExtended",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "previous_end_time",
+            "type": "i64"
+          },
+          {
+            "name": "new_end_time",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6002,
       "name": "AuctionFinalized",
-      "msg": "Auction already finalized"
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "outcome",
+            "type": {
+              "defined": {
+                "name": "AuctionOutcome"
+              }
+            }
+          },
+          {
+            "name": "winner",
+            "type": "pubkey"
+          },
+          {
+            "name": "final_bid",
+            "type": "u64"
+          },
+          {
+            "name": "clearing_price",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6003,
-      "name": "AuctionNotFinalized",
-      "msg": "Auction not finalized"
+      "name": "AuctionHouse",
+      "docs": [
+        "Global auction state for one auction instance."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "auction_id",
+            "type": "u64"
+          },
+          {
+            "name": "min_bid",
+            "type": "u64"
+          },
+          {
+            "name": "min_increment",
+            "type": "u64"
+          },
+          {
+            "name": "auction_type",
+            "type": {
+              "defined": {
+                "name": "AuctionType"
+              }
+            }
+          },
+          {
+            "name": "tie_break",
+            "type": {
+              "defined": {
+                "name": "TieBreak"
+              }
+            }
+          },
+          {
+            "name": "start_price",
+            "docs": [
+              "Dutch auctions: opening ask, decaying toward `min_bid` as the floor."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "price_decrement",
+            "docs": [
+              "Dutch auctions: amount the ask drops every `decay_interval` seconds."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "decay_interval",
+            "type": "i64"
+          },
+          {
+            "name": "buy_now_price",
+            "docs": [
+              "Price at which a bidder can end the auction at once; zero disables it."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "withdrawal_policy",
+            "docs": [
+              "Whether bidders may pull a sealed bid before `end_time`."
+            ],
+            "type": {
+              "defined": {
+                "name": "WithdrawalPolicy"
+              }
+            }
+          },
+          {
+            "name": "withdrawal_penalty_bps",
+            "docs": [
+              "Share of the deposit kept for the seller under `AllowedWithPenalty`."
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "payment_mint",
+            "docs": [
+              "SPL mint bids are denominated in; `Pubkey::default()` means lamports."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "item_mint",
+            "docs": [
+              "Mint of the escrowed lot; `Pubkey::default()` when nothing is escrowed."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "item_amount",
+            "type": "u64"
+          },
+          {
+            "name": "unit_count",
+            "docs": [
+              "Multi-unit auctions: number of equal lots `item_amount` is split into."
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "units_sold",
+            "docs": [
+              "Multi-unit auctions: lots allocated to winners at finalization."
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "reserve_commitment",
+            "docs": [
+              "Hash of the sealed reserve price; all zeroes when there is no reserve."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "reserve_price",
+            "type": "u64"
+          },
+          {
+            "name": "reserve_revealed",
+            "type": "bool"
+          },
+          {
+            "name": "highest_bid",
+            "type": "u64"
+          },
+          {
+            "name": "second_highest_bid",
+            "docs": [
+              "Runner-up revealed amount, tracked for second-price settlement."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "clearing_price",
+            "docs": [
+              "Price the winner pays, fixed at finalization. Per unit in multi-unit",
+              "auctions."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "winner",
+            "type": "pubkey"
+          },
+          {
+            "name": "winner_committed_at",
+            "docs": [
+              "`committed_at` of the current winner's bid, used for tie-breaking."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "start_time",
+            "type": "i64"
+          },
+          {
+            "name": "end_time",
+            "type": "i64"
+          },
+          {
+            "name": "reveal_duration",
+            "docs": [
+              "Seconds after `end_time` during which committed bids may be revealed."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "extension_window",
+            "docs": [
+              "Bids landing within this many seconds of `end_time` extend the auction."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "extension_duration",
+            "docs": [
+              "Seconds added to `end_time` by each late bid."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "hard_end_time",
+            "docs": [
+              "Soft-close extensions never push `end_time` past this cap."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "er_validator",
+            "docs": [
+              "ER/PER validator bids of this auction must be delegated to."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "commit_frequency_ms",
+            "docs": [
+              "How often the validator commits delegated bids back; zero uses the SDK default."
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "bidder_count",
+            "type": "u32"
+          },
+          {
+            "name": "open_bid_count",
+            "docs": [
+              "Sealed-bid accounts not yet closed, each possibly holding a refund.",
+              "Must reach zero before the auction can be closed."
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "committed_count",
+            "docs": [
+              "Committed bids seen by settlement; commits themselves happen on the",
+              "rollup and cannot write here."
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "settled_count",
+            "docs": [
+              "Bid accounts processed by settlement, committed or not."
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "forfeited_amount",
+            "docs": [
+              "Deposits of bids left unrevealed past the reveal deadline, plus",
+              "withdrawal penalties; paid to the seller."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "outcome",
+            "type": {
+              "defined": {
+                "name": "AuctionOutcome"
+              }
+            }
+          },
+          {
+            "name": "finalized",
+            "type": "bool"
+          },
+          {
+            "name": "proceeds_claimed",
+            "type": "bool"
+          },
+          {
+            "name": "item_claimed",
+            "type": "bool"
+          },
+          {
+            "name": "fee_bps",
+            "docs": [
+              "Platform fee copied from the config at creation, so a later config",
+              "change cannot reprice a listed sale. Zero if no config existed yet."
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "proceeds_splits",
+            "docs": [
+              "Creators or co-owners paid a share of the seller proceeds; the",
+              "authority keeps the rest."
+            ],
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "ProceedsSplit"
+                }
+              }
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "vault_bump",
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6004,
-      "name": "BidIncrementTooSmall",
-      "msg": "Bid increment is too small"
+      "name": "AuctionOutcome",
+      "docs": [
+        "Result recorded when an auction is finalized."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Pending"
+          },
+          {
+            "name": "Sold"
+          },
+          {
+            "name": "NoBids"
+          },
+          {
+            "name": "ReserveNotMet"
+          },
+          {
+            "name": "Cancelled"
+          },
+          {
+            "name": "BoughtNow"
+          }
+        ]
+      }
     },
     {
-      "code": 6005,
-      "name": "BidBelowMinimum",
-      "msg": "Bid is below auction minimum"
+      "name": "AuctionType",
+      "docs": [
+        "Pricing rule applied when an auction is finalized."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "FirstPrice"
+          },
+          {
+            "name": "SecondPrice"
+          },
+          {
+            "name": "Dutch"
+          },
+          {
+            "name": "English"
+          },
+          {
+            "name": "MultiUnit"
+          }
+        ]
+      }
     },
     {
-      "code": 6006,
-      "name": "CannotDelegate",
-      "msg": "Cannot delegate this account in its current state"
+      "name": "BidCommitted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "bidder",
+            "type": "pubkey"
+          },
+          {
+            "name": "deposited",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6007,
-      "name": "AccountNotDelegated",
-      "msg": "Bid account is not delegated"
+      "name": "BidForfeited",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "bidder",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6008,
-      "name": "BidNotCommitted",
-      "msg": "Bid account is not committed"
+      "name": "BidRevealed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "bidder",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6009,
-      "name": "BidAlreadySettled",
-      "msg": "Bid account has already been settled"
+      "name": "BidSettled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "bidder",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "current_highest_bid",
+            "type": "u64"
+          },
+          {
+            "name": "current_winner",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6010,
-      "name": "UnsettledCommittedBids",
-      "msg": "There are unsettled committed bids"
+      "name": "BidStatus",
+      "docs": [
+        "Lifecycle of an individual bidder's private bid account."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Ready"
+          },
+          {
+            "name": "Active"
+          },
+          {
+            "name": "Committed"
+          }
+        ]
+      }
     },
     {
-      "code": 6011,
-      "name": "BidAuctionMismatch",
-      "msg": "Bid account is linked to a different auction"
+      "name": "BidSubmitted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "bidder",
+            "type": "pubkey"
+          },
+          {
+            "name": "deposited",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6012,
-      "name": "InvalidDuration",
-      "msg": "Duration must be greater than zero"
+      "name": "BidWithdrawn",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "bidder",
+            "type": "pubkey"
+          },
+          {
+            "name": "refunded",
+            "type": "u64"
+          },
+          {
+            "name": "penalty",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6013,
-      "name": "InvalidMinBid",
-      "msg": "Minimum bid must be greater than zero"
+      "name": "BoughtNow",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6014,
-      "name": "MathOverflow",
-      "msg": "Integer overflow"
+      "name": "CreateAuctionParams",
+      "docs": [
+        "Instruction arguments for `create_auction`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction_id",
+            "type": "u64"
+          },
+          {
+            "name": "min_bid",
+            "type": "u64"
+          },
+          {
+            "name": "min_increment",
+            "type": "u64"
+          },
+          {
+            "name": "start_time",
+            "docs": [
+              "Unix timestamp bidding opens at; zero starts the auction immediately."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "duration",
+            "docs": [
+              "Bidding length in seconds, counted from the start time."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "reveal_duration",
+            "type": "i64"
+          },
+          {
+            "name": "auction_type",
+            "type": {
+              "defined": {
+                "name": "AuctionType"
+              }
+            }
+          },
+          {
+            "name": "tie_break",
+            "type": {
+              "defined": {
+                "name": "TieBreak"
+              }
+            }
+          },
+          {
+            "name": "unit_count",
+            "docs": [
+              "Lots the escrowed item is split into; multi-unit auctions only."
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "start_price",
+            "docs": [
+              "Dutch schedule; ignored for other auction types."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "price_decrement",
+            "type": "u64"
+          },
+          {
+            "name": "decay_interval",
+            "type": "i64"
+          },
+          {
+            "name": "buy_now_price",
+            "docs": [
+              "Buy-it-now price; zero for none."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "withdrawal_policy",
+            "type": {
+              "defined": {
+                "name": "WithdrawalPolicy"
+              }
+            }
+          },
+          {
+            "name": "withdrawal_penalty_bps",
+            "docs": [
+              "Basis points of the deposit forfeited on withdrawal; penalty policy only."
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "item_amount",
+            "docs": [
+              "Amount of `item_mint` to escrow; ignored when no item accounts are passed."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserve_commitment",
+            "docs": [
+              "Hash of (reserve price, salt, auction); all zeroes for no reserve."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "extension_window",
+            "docs": [
+              "Soft-close window before `end_time`; zero disables extensions."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "extension_duration",
+            "type": "i64"
+          },
+          {
+            "name": "max_extension",
+            "docs": [
+              "Most seconds extensions may add on top of `duration` in total."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "er_validator",
+            "docs": [
+              "ER/PER validator to delegate bids to; `Pubkey::default()` picks the",
+              "devnet default."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "commit_frequency_ms",
+            "docs": [
+              "Commit frequency for delegated bids; zero uses the SDK default."
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "proceeds_splits",
+            "docs": [
+              "Up to `MAX_PROCEEDS_SPLITS` shares of the seller proceeds."
+            ],
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "ProceedsSplit"
+                }
+              }
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6015,
-      "name": "ProceedsAlreadyClaimed",
-      "msg": "Seller proceeds have already been claimed"
+      "name": "DutchPriceAccepted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "bidder",
+            "type": "pubkey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6016,
-      "name": "NoWinningBid",
-      "msg": "No winning bid in this auction"
+      "name": "EscrowDeposited",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "bidder",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "deposited",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6017,
-      "name": "WinnerNoRefund",
-      "msg": "Winner cannot claim refund"
+      "name": "ItemClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "recipient",
+            "type": "pubkey"
+          },
+          {
+            "name": "item_mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6018,
-      "name": "RefundAlreadyClaimed",
-      "msg": "Refund already claimed"
+      "name": "OpenBidPlaced",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "bidder",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "previous_winner",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6019,
-      "name": "NoRefundAvailable",
-      "msg": "No refundable amount available"
+      "name": "ProceedsDistributed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "recipient",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6020,
-      "name": "InsufficientVaultBalance",
-      "msg": "Vault balance is insufficient"
+      "name": "ProceedsSplit",
+      "docs": [
+        "A recipient's share of the seller proceeds, in basis points."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "recipient",
+            "type": "pubkey"
+          },
+          {
+            "name": "bps",
+            "type": "u16"
+          }
+        ]
+      }
     },
     {
-      "code": 6021,
-      "name": "CloseNotAllowed",
-      "msg": "Bid account cannot be closed yet"
-    }
-  ],
-  "types": [
-    {
-      "name": "AuctionCreated",
+      "name": "ProtocolConfig",
+      "docs": [
+        "Protocol-wide settings shared by every auction."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "auction",
+            "name": "admin",
             "type": "pubkey"
           },
           {
-            "name": "authority",
-            "type": "pubkey"
+            "name": "fee_bps",
+            "docs": [
+              "Platform fee taken from seller proceeds, in basis points."
+            ],
+            "type": "u16"
           },
           {
-            "name": "auction_id",
-            "type": "u64"
+            "name": "fee_recipient",
+            "type": "pubkey"
           },
           {
-            "name": "min_bid",
-            "type": "u64"
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ProtocolConfigUpdated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "pubkey"
           },
           {
-            "name": "min_increment",
-            "type": "u64"
+            "name": "fee_bps",
+            "type": "u16"
           },
           {
-            "name": "ends_at",
-            "type": "i64"
+            "name": "fee_recipient",
+            "type": "pubkey"
           }
         ]
       }
     },
     {
-      "name": "AuctionFinalized",
+      "name": "RefundClaimed",
       "type": {
         "kind": "struct",
         "fields": [
@@ -1111,85 +5565,122 @@ export const IDL =
             "type": "pubkey"
           },
           {
-            "name": "winner",
+            "name": "bidder",
             "type": "pubkey"
           },
           {
-            "name": "final_bid",
+            "name": "amount",
             "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "AuctionHouse",
+      "name": "ReserveRevealed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
+          },
+          {
+            "name": "reserve_price",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "SealedBid",
       "docs": [
-        "Global auction state for one auction instance."
+        "Bidder-specific sealed bid state. Delegated/committed via ER/PER flow.",
+        "",
+        "While bidding is open only `commitment` (a hash of amount, salt and",
+        "bidder) is stored; `amount` stays zero until `reveal_bid`."
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "authority",
+            "name": "auction",
             "type": "pubkey"
           },
           {
-            "name": "auction_id",
-            "type": "u64"
+            "name": "bidder",
+            "type": "pubkey"
           },
           {
-            "name": "min_bid",
-            "type": "u64"
+            "name": "commitment",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
           },
           {
-            "name": "min_increment",
+            "name": "amount",
             "type": "u64"
           },
           {
-            "name": "highest_bid",
-            "type": "u64"
+            "name": "quantity",
+            "docs": [
+              "Lots requested in a multi-unit auction; `amount` is then the unit price."
+            ],
+            "type": "u32"
           },
           {
-            "name": "winner",
-            "type": "pubkey"
+            "name": "deposited",
+            "type": "u64"
           },
           {
-            "name": "end_time",
-            "type": "i64"
+            "name": "status",
+            "type": {
+              "defined": {
+                "name": "BidStatus"
+              }
+            }
           },
           {
-            "name": "bidder_count",
-            "type": "u32"
+            "name": "committed",
+            "type": "bool"
           },
           {
-            "name": "committed_count",
-            "type": "u32"
+            "name": "committed_at",
+            "docs": [
+              "Unix timestamp of the first commit, used for tie-breaking."
+            ],
+            "type": "i64"
           },
           {
-            "name": "settled_count",
-            "type": "u32"
+            "name": "revealed",
+            "type": "bool"
           },
           {
-            "name": "finalized",
+            "name": "settled",
             "type": "bool"
           },
           {
-            "name": "proceeds_claimed",
+            "name": "refund_claimed",
             "type": "bool"
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "session_charged",
+            "docs": [
+              "Part of `deposited` already charged to the bidder's session key."
+            ],
+            "type": "u64"
           },
           {
-            "name": "vault_bump",
+            "name": "bump",
             "type": "u8"
           }
         ]
       }
     },
     {
-      "name": "BidCommitted",
+      "name": "SellerProceedsClaimed",
       "type": {
         "kind": "struct",
         "fields": [
@@ -1198,86 +5689,136 @@ export const IDL =
             "type": "pubkey"
           },
           {
-            "name": "bidder",
+            "name": "authority",
             "type": "pubkey"
           },
           {
             "name": "amount",
+            "docs": [
+              "Paid to the authority, net of the platform fee."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "fee",
             "type": "u64"
+          },
+          {
+            "name": "fee_recipient",
+            "type": "pubkey"
           }
         ]
       }
     },
     {
-      "name": "BidSettled",
+      "name": "SessionCreated",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "auction",
+            "name": "bidder",
             "type": "pubkey"
           },
           {
-            "name": "bidder",
+            "name": "session_signer",
             "type": "pubkey"
           },
           {
-            "name": "amount",
-            "type": "u64"
+            "name": "expires_at",
+            "type": "i64"
           },
           {
-            "name": "current_highest_bid",
+            "name": "max_spend",
             "type": "u64"
-          },
-          {
-            "name": "current_winner",
-            "type": "pubkey"
           }
         ]
       }
     },
     {
-      "name": "BidStatus",
+      "name": "SessionKey",
       "docs": [
-        "Lifecycle of an individual bidder's private bid account."
+        "Delegated signing key a bidder registers on L1 so a hot key can submit and",
+        "commit their sealed bids without a wallet prompt each time. Submitting",
+        "charges the session, so it has to be delegated alongside bids it signs for",
+        "inside the rollup."
       ],
       "type": {
-        "kind": "enum",
-        "variants": [
+        "kind": "struct",
+        "fields": [
           {
-            "name": "Ready"
+            "name": "bidder",
+            "type": "pubkey"
           },
           {
-            "name": "Active"
+            "name": "session_signer",
+            "type": "pubkey"
           },
           {
-            "name": "Committed"
+            "name": "expires_at",
+            "type": "i64"
+          },
+          {
+            "name": "max_spend",
+            "docs": [
+              "Most escrow the session may put behind bids, across all of them."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "spent",
+            "docs": [
+              "Escrow the session has put behind bids so far."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
           }
         ]
       }
     },
     {
-      "name": "BidSubmitted",
+      "name": "SessionRevoked",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "auction",
+            "name": "bidder",
             "type": "pubkey"
           },
           {
-            "name": "bidder",
+            "name": "session_signer",
             "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TieBreak",
+      "docs": [
+        "Rule deciding the winner between equal highest bids."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "EarliestCommit"
           },
           {
-            "name": "amount",
-            "type": "u64"
+            "name": "LowestBidderKey"
           }
         ]
       }
     },
     {
-      "name": "RefundClaimed",
+      "name": "UnitBook",
+      "docs": [
+        "Revealed bids of a multi-unit auction that still fall within the listed",
+        "units, best first. A bid whose predecessors already fill every unit can",
+        "never win again and is dropped, so the book holds at most `unit_count`",
+        "entries and its contents do not depend on settlement order."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
@@ -1286,86 +5827,94 @@ export const IDL =
             "type": "pubkey"
           },
           {
-            "name": "bidder",
-            "type": "pubkey"
+            "name": "entries",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "UnitBookEntry"
+                }
+              }
+            }
           },
           {
-            "name": "amount",
-            "type": "u64"
+            "name": "bump",
+            "type": "u8"
           }
         ]
       }
     },
     {
-      "name": "SealedBid",
-      "docs": [
-        "Bidder-specific sealed bid state. Delegated/committed via ER/PER flow."
-      ],
+      "name": "UnitBookEntry",
       "type": {
         "kind": "struct",
         "fields": [
-          {
-            "name": "auction",
-            "type": "pubkey"
-          },
           {
             "name": "bidder",
             "type": "pubkey"
           },
           {
-            "name": "amount",
+            "name": "unit_price",
             "type": "u64"
           },
           {
-            "name": "deposited",
-            "type": "u64"
+            "name": "quantity",
+            "type": "u32"
           },
           {
-            "name": "status",
-            "type": {
-              "defined": {
-                "name": "BidStatus"
-              }
-            }
+            "name": "committed_at",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "UnitsClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auction",
+            "type": "pubkey"
           },
           {
-            "name": "committed",
-            "type": "bool"
+            "name": "bidder",
+            "type": "pubkey"
           },
           {
-            "name": "settled",
-            "type": "bool"
+            "name": "units",
+            "type": "u32"
           },
           {
-            "name": "refund_claimed",
-            "type": "bool"
+            "name": "item_amount",
+            "type": "u64"
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "refund",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "SellerProceedsClaimed",
+      "name": "WithdrawalPolicy",
+      "docs": [
+        "Whether a sealed bid may be withdrawn while bidding is open."
+      ],
       "type": {
-        "kind": "struct",
-        "fields": [
+        "kind": "enum",
+        "variants": [
           {
-            "name": "auction",
-            "type": "pubkey"
+            "name": "Forbidden"
           },
           {
-            "name": "authority",
-            "type": "pubkey"
+            "name": "Allowed"
           },
           {
-            "name": "amount",
-            "type": "u64"
+            "name": "AllowedWithPenalty"
           }
         ]
       }
     }
   ]
-} as const satisfies Idl;
+}
+ as const satisfies Idl;
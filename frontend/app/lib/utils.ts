@@ -1,4 +1,4 @@
-import { AnchorProvider } from '@coral-xyz/anchor';
+import { AnchorProvider, BN } from '@coral-xyz/anchor';
 import { PublicKey } from '@solana/web3.js';
 
 let cachedProvider: AnchorProvider | null = null;
@@ -11,9 +11,9 @@ export function setProvider(provider: AnchorProvider) {
   cachedProvider = provider;
 }
 
-export function getAuctionPDA(authority: PublicKey): PublicKey {
+export function getAuctionPDA(authority: PublicKey, auctionId: BN): PublicKey {
   return PublicKey.findProgramAddressSync(
-    [Buffer.from('auction'), authority.toBuffer()],
+    [Buffer.from('auction'), authority.toBuffer(), auctionId.toArrayLike(Buffer, 'le', 8)],
     new PublicKey('HK92WjG3LE4JCWi5mU7pAvLz1JFBUwwh1v3obB9ajwnE')
   )[0];
 }
@@ -1,9 +1,45 @@
+use anchor_lang::prelude::*;
+use ephemeral_rollups_sdk::access_control::structs::{
+    ACCOUNT_SIGNATURES_FLAG, TX_BALANCES_FLAG, TX_LOGS_FLAG, TX_MESSAGE_FLAG,
+};
+
+/// PDA seed of the singleton protocol config.
+pub const CONFIG_SEED: &[u8] = b"config";
 /// PDA seed prefix for each auction account.
 pub const AUCTION_SEED: &[u8] = b"auction";
 /// PDA seed prefix for each bidder's sealed bid account.
 pub const BID_SEED: &[u8] = b"bid";
 /// PDA seed prefix for per-auction escrow vault account.
 pub const VAULT_SEED: &[u8] = b"vault";
+/// PDA seed prefix for the vault's token account in SPL-denominated auctions.
+pub const VAULT_TOKEN_SEED: &[u8] = b"vault_token";
+/// PDA seed prefix for the token account escrowing the auctioned item.
+pub const ITEM_VAULT_SEED: &[u8] = b"item_vault";
+/// PDA seed prefix for the order book of a multi-unit auction.
+pub const UNIT_BOOK_SEED: &[u8] = b"unit_book";
+/// PDA seed prefix for a bidder's session key.
+pub const SESSION_SEED: &[u8] = b"session";
+
+/// Most bids `settle_committed_bids` accepts per transaction. Each bid costs a
+/// PDA derivation plus a deserialize/serialize round trip, and the account
+/// list must also fit in a single transaction.
+pub const MAX_SETTLE_BATCH: usize = 20;
+
+/// Most units a multi-unit auction can list. Every bid wins at least one
+/// unit, so this also bounds the entries `UnitBook` has to hold.
+pub const MAX_AUCTION_UNITS: u32 = 32;
+
+/// Most proceeds split recipients an auction can register besides its authority.
+pub const MAX_PROCEEDS_SPLITS: usize = 5;
+
+/// Basis-point denominator for percentage settings such as withdrawal penalties.
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+/// PER visibility granted to a bidder and the auction authority on a sealed
+/// bid's permission. Neither gets the authority flag, so only the program,
+/// signing as the bid PDA, can change who may read the bid.
+pub const BID_VIEWER_FLAGS: u8 =
+    TX_LOGS_FLAG | TX_BALANCES_FLAG | TX_MESSAGE_FLAG | ACCOUNT_SIGNATURES_FLAG;
 
-/// Default validator used for ER/PER delegation on devnet.
-pub const DEVNET_ASIA_ER_VALIDATOR: &str = "MAS1Dt9qreoRMQ14YQuhg8UTZMMzDdKhmkZMECCzk57";
+/// Devnet validator used for ER/PER delegation when an auction names none.
+pub const DEVNET_ASIA_ER_VALIDATOR: Pubkey = pubkey!("MAS1Dt9qreoRMQ14YQuhg8UTZMMzDdKhmkZMECCzk57");
@@ -1,19 +1,92 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use ephemeral_rollups_sdk::access_control::structs::PERMISSION_SEED;
 use ephemeral_rollups_sdk::anchor::{commit, delegate};
+use ephemeral_rollups_sdk::consts::PERMISSION_PROGRAM_ID;
+use ephemeral_rollups_sdk::pda::{
+    DELEGATE_BUFFER_TAG, DELEGATION_METADATA_TAG, DELEGATION_RECORD_TAG,
+};
 
-use crate::constants::{AUCTION_SEED, BID_SEED, VAULT_SEED};
+use crate::constants::{
+    AUCTION_SEED, BID_SEED, CONFIG_SEED, ITEM_VAULT_SEED, SESSION_SEED, UNIT_BOOK_SEED, VAULT_SEED,
+    VAULT_TOKEN_SEED,
+};
 use crate::errors::AuctionError;
-use crate::state::{AuctionHouse, BidStatus, SealedBid};
+use crate::program::Ephemeralbid;
+use crate::state::{
+    AuctionHouse, BidStatus, CreateAuctionParams, ProtocolConfig, SealedBid, SessionKey, UnitBook,
+};
+
+/// Creates the protocol config. Only the program's upgrade authority can do
+/// this, which keeps the first admin from being front-run.
+///
+/// `fee_recipient` must already be rent exempt, so a lamport fee smaller than
+/// the rent-exempt minimum can still be paid to it.
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProtocolConfig::LEN,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Ephemeralbid>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ AuctionError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(
+        constraint = Rent::get()?.is_exempt(fee_recipient.lamports(), fee_recipient.data_len())
+            @ AuctionError::FeeRecipientNotRentExempt
+    )]
+    /// CHECK: Only its key and balance are used.
+    pub fee_recipient: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Changes the protocol fee, its recipient or the admin itself. The recipient
+/// must be rent exempt, as in `InitializeConfig`.
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        has_one = admin @ AuctionError::Unauthorized,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(
+        constraint = Rent::get()?.is_exempt(fee_recipient.lamports(), fee_recipient.data_len())
+            @ AuctionError::FeeRecipientNotRentExempt
+    )]
+    /// CHECK: Only its key and balance are used.
+    pub fee_recipient: UncheckedAccount<'info>,
+    pub admin: Signer<'info>,
+}
 
 /// Initializes a new auction and its escrow vault PDA.
+///
+/// Passing `payment_mint` denominates the auction in that SPL mint and creates
+/// a vault-owned token account for it; omitting it escrows lamports. Passing
+/// the `item_*` accounts moves the lot being sold into a vault-owned escrow.
+/// Multi-unit auctions also pass `unit_book` to hold their accepted bids.
 #[derive(Accounts)]
-#[instruction(auction_id: u64)]
+#[instruction(params: CreateAuctionParams)]
 pub struct CreateAuction<'info> {
+    #[account(seeds = [CONFIG_SEED], bump)]
+    /// CHECK: Protocol config PDA, read for the fee once it has been initialized.
+    pub config: UncheckedAccount<'info>,
     #[account(
         init,
         payer = authority,
         space = 8 + AuctionHouse::LEN,
-        seeds = [AUCTION_SEED, authority.key().as_ref(), &auction_id.to_le_bytes()],
+        seeds = [AUCTION_SEED, authority.key().as_ref(), &params.auction_id.to_le_bytes()],
         bump
     )]
     pub auction_house: Account<'info, AuctionHouse>,
@@ -26,16 +99,119 @@ pub struct CreateAuction<'info> {
     )]
     /// CHECK: Program-owned lamport vault PDA.
     pub vault: UncheckedAccount<'info>,
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [VAULT_TOKEN_SEED, auction_house.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = vault,
+        token::token_program = token_program
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub item_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [ITEM_VAULT_SEED, auction_house.key().as_ref()],
+        bump,
+        token::mint = item_mint,
+        token::authority = vault,
+        token::token_program = item_token_program
+    )]
+    pub item_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub seller_item_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + UnitBook::LEN,
+        seeds = [UNIT_BOOK_SEED, auction_house.key().as_ref()],
+        bump
+    )]
+    pub unit_book: Option<Account<'info, UnitBook>>,
     #[account(mut)]
     pub authority: Signer<'info>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub item_token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Registers a session key that can sign bids on the bidder's behalf.
+#[derive(Accounts)]
+pub struct CreateSession<'info> {
+    #[account(
+        init,
+        payer = bidder,
+        space = 8 + SessionKey::LEN,
+        seeds = [SESSION_SEED, bidder.key().as_ref()],
+        bump
+    )]
+    pub session: Account<'info, SessionKey>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+/// Revokes the bidder's session key and returns its rent.
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(
+        mut,
+        close = bidder,
+        has_one = bidder,
+        seeds = [SESSION_SEED, bidder.key().as_ref()],
+        bump = session.bump
+    )]
+    pub session: Account<'info, SessionKey>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+}
+
+/// Delegates the bidder's session key to the validator of the rollup it is
+/// going to sign bids on.
+#[delegate]
+#[derive(Accounts)]
+pub struct DelegateSession<'info> {
+    #[account(mut, has_one = bidder, del)]
+    pub session: Account<'info, SessionKey>,
+    /// CHECK: Only its address is used, as the validator to delegate to.
+    pub validator: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+}
+
+/// Commits the bidder's delegated session key and returns it to L1.
+#[commit]
+#[derive(Accounts)]
+pub struct UndelegateSession<'info> {
+    #[account(
+        mut,
+        has_one = bidder,
+        seeds = [SESSION_SEED, bidder.key().as_ref()],
+        bump = session.bump
+    )]
+    pub session: Account<'info, SessionKey>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+}
+
 /// Creates bidder-specific sealed bid account for an auction.
+///
+/// Registration writes the auction, which L1 cannot do while it is delegated,
+/// so the auction is taken unchecked to fail with `AuctionDelegated` instead
+/// of an owner error. The handler deserializes it.
 #[derive(Accounts)]
 pub struct InitializeSealedBid<'info> {
-    #[account(mut)]
-    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        constraint = auction_house.owner != &ephemeral_rollups_sdk::id()
+            @ AuctionError::AuctionDelegated,
+        owner = crate::ID
+    )]
+    /// CHECK: Owner checked here; deserialized as an `AuctionHouse` by the handler.
+    pub auction_house: UncheckedAccount<'info>,
     #[account(
         init,
         payer = bidder,
@@ -49,24 +225,163 @@ pub struct InitializeSealedBid<'info> {
     pub system_program: Program<'info, System>,
 }
 
-/// Delegates sealed bid account to ER/PER.
+/// Delegates sealed bid account to ER/PER, on the validator the auction names.
+///
+/// Sealed bids only delegate once their permission has been delegated through
+/// `delegate_bid_permission`, so the rollup never holds a bid readable by all.
 #[delegate]
 #[derive(Accounts)]
 pub struct DelegateBid<'info> {
+    pub auction_house: Account<'info, AuctionHouse>,
     #[account(
         mut,
         has_one = bidder,
+        constraint = sealed_bid.auction == auction_house.key() @ AuctionError::BidAuctionMismatch,
         constraint = sealed_bid.status == BidStatus::Active @ AuctionError::CannotDelegate,
         del
     )]
     pub sealed_bid: Account<'info, SealedBid>,
+    #[account(
+        seeds = [PERMISSION_SEED, sealed_bid.key().as_ref()],
+        bump,
+        seeds::program = PERMISSION_PROGRAM_ID
+    )]
+    /// CHECK: Bid permission PDA; only its owner is read.
+    pub permission: UncheckedAccount<'info>,
+    #[account(address = auction_house.er_validator @ AuctionError::ValidatorMismatch)]
+    /// CHECK: Only its address is used, pinned to the auction's validator.
+    pub validator: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+}
+
+/// Creates the PER permission that keeps a sealed bid readable only by its
+/// bidder and the auction authority.
+#[derive(Accounts)]
+pub struct CreateBidPermission<'info> {
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        has_one = bidder,
+        constraint = sealed_bid.auction == auction_house.key() @ AuctionError::BidAuctionMismatch,
+        seeds = [BID_SEED, auction_house.key().as_ref(), bidder.key().as_ref()],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    #[account(
+        mut,
+        seeds = [PERMISSION_SEED, sealed_bid.key().as_ref()],
+        bump,
+        seeds::program = permission_program.key()
+    )]
+    /// CHECK: Bid permission PDA, owned by the permission program.
+    pub permission: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(address = PERMISSION_PROGRAM_ID)]
+    /// CHECK: The ER permission program.
+    pub permission_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Delegates a sealed bid's permission to the auction's validator, so the
+/// rollup enforces it. Must run before `delegate_bid`.
+#[derive(Accounts)]
+pub struct DelegateBidPermission<'info> {
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        has_one = bidder,
+        constraint = sealed_bid.auction == auction_house.key() @ AuctionError::BidAuctionMismatch,
+        seeds = [BID_SEED, auction_house.key().as_ref(), bidder.key().as_ref()],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    #[account(
+        mut,
+        seeds = [PERMISSION_SEED, sealed_bid.key().as_ref()],
+        bump,
+        seeds::program = permission_program.key()
+    )]
+    /// CHECK: Bid permission PDA, owned by the permission program.
+    pub permission: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [DELEGATE_BUFFER_TAG, permission.key().as_ref()],
+        bump,
+        seeds::program = permission_program.key()
+    )]
+    /// CHECK: Delegation buffer, validated by the delegation program.
+    pub buffer_permission: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [DELEGATION_RECORD_TAG, permission.key().as_ref()],
+        bump,
+        seeds::program = delegation_program.key()
+    )]
+    /// CHECK: Delegation record, validated by the delegation program.
+    pub delegation_record_permission: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [DELEGATION_METADATA_TAG, permission.key().as_ref()],
+        bump,
+        seeds::program = delegation_program.key()
+    )]
+    /// CHECK: Delegation metadata, validated by the delegation program.
+    pub delegation_metadata_permission: UncheckedAccount<'info>,
+    #[account(address = auction_house.er_validator @ AuctionError::ValidatorMismatch)]
+    /// CHECK: Only its address is used, pinned to the auction's validator.
+    pub validator: UncheckedAccount<'info>,
     #[account(mut)]
     pub bidder: Signer<'info>,
+    #[account(address = PERMISSION_PROGRAM_ID)]
+    /// CHECK: The ER permission program.
+    pub permission_program: UncheckedAccount<'info>,
+    #[account(address = ephemeral_rollups_sdk::id())]
+    /// CHECK: The delegation program.
+    pub delegation_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Delegates an English auction to ER, on the validator the auction names, so
+/// open bids can be placed against it inside the rollup.
+#[delegate]
+#[derive(Accounts)]
+pub struct DelegateAuction<'info> {
+    #[account(mut, has_one = authority, del)]
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(address = auction_house.er_validator @ AuctionError::ValidatorMismatch)]
+    /// CHECK: Only its address is used, pinned to the auction's validator.
+    pub validator: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
 }
 
-/// Submits/updates a private sealed bid and tops up escrow deposit.
+/// Submits/updates a sealed bid commitment against the pre-funded escrow.
+/// Signed by the bidder, or by their session key when `session` is passed;
+/// the session is charged for the deposit, so inside the rollup it has to be
+/// delegated too.
 #[derive(Accounts)]
 pub struct SubmitSealedBid<'info> {
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        constraint = sealed_bid.auction == auction_house.key() @ AuctionError::BidAuctionMismatch,
+        seeds = [BID_SEED, auction_house.key().as_ref(), sealed_bid.bidder.as_ref()],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    #[account(
+        mut,
+        seeds = [SESSION_SEED, sealed_bid.bidder.as_ref()],
+        bump = session.bump
+    )]
+    pub session: Option<Account<'info, SessionKey>>,
+    /// The bidder, or the signer of their session key.
+    pub signer: Signer<'info>,
+}
+
+/// Places a public English bid, topping up the bidder's escrow as needed.
+#[derive(Accounts)]
+pub struct PlaceOpenBid<'info> {
     #[account(mut)]
     pub auction_house: Account<'info, AuctionHouse>,
     #[account(
@@ -84,15 +399,120 @@ pub struct SubmitSealedBid<'info> {
     )]
     /// CHECK: Vault PDA validated by seeds + bump.
     pub vault: UncheckedAccount<'info>,
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [VAULT_TOKEN_SEED, auction_house.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub bidder_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     #[account(mut)]
     pub bidder: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-/// Marks delegated bid as committed after private phase.
-#[commit]
+/// Pre-funds a bidder's escrow on L1 ahead of sealed or delegated bidding.
+///
+/// Taken unchecked like in `InitializeSealedBid`, so funding a delegated
+/// auction fails with `AuctionDelegated`.
 #[derive(Accounts)]
-pub struct CommitBid<'info> {
+pub struct DepositToEscrow<'info> {
+    #[account(
+        constraint = auction_house.owner != &ephemeral_rollups_sdk::id()
+            @ AuctionError::AuctionDelegated,
+        owner = crate::ID
+    )]
+    /// CHECK: Owner checked here; deserialized as an `AuctionHouse` by the handler.
+    pub auction_house: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        has_one = bidder,
+        constraint = sealed_bid.auction == auction_house.key() @ AuctionError::BidAuctionMismatch,
+        seeds = [BID_SEED, auction_house.key().as_ref(), bidder.key().as_ref()],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, auction_house.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Vault PDA validated by seeds + bump.
+    pub vault: UncheckedAccount<'info>,
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [VAULT_TOKEN_SEED, auction_house.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub bidder_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Places an English bid covered by the bidder's existing escrow. Nothing is
+/// transferred and the bid account is only read, so this runs inside the
+/// rollup against a delegated auction.
+#[derive(Accounts)]
+pub struct PlaceEscrowedBid<'info> {
+    #[account(mut)]
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        has_one = bidder,
+        constraint = sealed_bid.auction == auction_house.key() @ AuctionError::BidAuctionMismatch,
+        seeds = [BID_SEED, auction_house.key().as_ref(), bidder.key().as_ref()],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    pub bidder: Signer<'info>,
+}
+
+/// Accepts the current Dutch ask, paying into the vault via the bidder's escrow.
+#[derive(Accounts)]
+pub struct AcceptDutchPrice<'info> {
+    #[account(mut)]
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        has_one = bidder,
+        constraint = sealed_bid.auction == auction_house.key() @ AuctionError::BidAuctionMismatch,
+        seeds = [BID_SEED, auction_house.key().as_ref(), bidder.key().as_ref()],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, auction_house.key().as_ref()],
+        bump = auction_house.vault_bump
+    )]
+    /// CHECK: Vault PDA validated by seeds + bump.
+    pub vault: UncheckedAccount<'info>,
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [VAULT_TOKEN_SEED, auction_house.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub bidder_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Buys the lot at the buy-it-now price, paying into the vault via the
+/// buyer's escrow.
+#[derive(Accounts)]
+pub struct BuyNow<'info> {
     #[account(mut)]
     pub auction_house: Account<'info, AuctionHouse>,
     #[account(
@@ -103,13 +523,152 @@ pub struct CommitBid<'info> {
         bump = sealed_bid.bump
     )]
     pub sealed_bid: Account<'info, SealedBid>,
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, auction_house.key().as_ref()],
+        bump = auction_house.vault_bump
+    )]
+    /// CHECK: Vault PDA validated by seeds + bump.
+    pub vault: UncheckedAccount<'info>,
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [VAULT_TOKEN_SEED, auction_house.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub bidder_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    #[account(mut)]
     pub bidder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Marks delegated bid as committed after private phase. Signed by the
+/// bidder, or by their session key when `session` is passed.
+#[commit]
+#[derive(Accounts)]
+pub struct CommitBid<'info> {
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        constraint = sealed_bid.auction == auction_house.key() @ AuctionError::BidAuctionMismatch,
+        seeds = [BID_SEED, auction_house.key().as_ref(), sealed_bid.bidder.as_ref()],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    #[account(
+        seeds = [SESSION_SEED, sealed_bid.bidder.as_ref()],
+        bump = session.bump
+    )]
+    pub session: Option<Account<'info, SessionKey>>,
+    /// The bidder, or the signer of their session key.
+    pub signer: Signer<'info>,
+}
+
+/// Commits a delegated bid and undelegates it back to L1 in one step.
+///
+/// The bidder can call this at any time. Once bidding has closed anyone can,
+/// so an abandoned delegated bid cannot keep settlement from finishing.
+#[commit]
+#[derive(Accounts)]
+pub struct CommitAndUndelegateBid<'info> {
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        constraint = sealed_bid.auction == auction_house.key() @ AuctionError::BidAuctionMismatch,
+        seeds = [BID_SEED, auction_house.key().as_ref(), sealed_bid.bidder.as_ref()],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+/// Commits a delegated auction's standings to L1 outside the periodic
+/// schedule.
+#[commit]
+#[derive(Accounts)]
+pub struct CommitAuction<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AUCTION_SEED,
+            auction_house.authority.as_ref(),
+            &auction_house.auction_id.to_le_bytes()
+        ],
+        bump = auction_house.bump
+    )]
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+/// Commits a delegated auction and returns it to L1 for finalization. The
+/// authority can do this at any time, anyone else once bidding has closed.
+#[commit]
+#[derive(Accounts)]
+pub struct UndelegateAuction<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AUCTION_SEED,
+            auction_house.authority.as_ref(),
+            &auction_house.auction_id.to_le_bytes()
+        ],
+        bump = auction_house.bump
+    )]
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+/// Opens a sealed bid's permission to everyone once bidding has closed and
+/// returns the permission to L1.
+#[commit]
+#[derive(Accounts)]
+pub struct ReleaseBidPermission<'info> {
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        constraint = sealed_bid.auction == auction_house.key() @ AuctionError::BidAuctionMismatch,
+        seeds = [BID_SEED, auction_house.key().as_ref(), sealed_bid.bidder.as_ref()],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    #[account(
+        mut,
+        seeds = [PERMISSION_SEED, sealed_bid.key().as_ref()],
+        bump,
+        seeds::program = permission_program.key()
+    )]
+    /// CHECK: Bid permission PDA, owned by the permission program.
+    pub permission: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(address = PERMISSION_PROGRAM_ID)]
+    /// CHECK: The ER permission program.
+    pub permission_program: UncheckedAccount<'info>,
 }
 
 /// L1-compatible commit path (without MagicBlock commit accounts).
 #[derive(Accounts)]
 pub struct CommitBidL1<'info> {
-    #[account(mut)]
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        has_one = bidder,
+        constraint = sealed_bid.auction == auction_house.key() @ AuctionError::BidAuctionMismatch,
+        seeds = [BID_SEED, auction_house.key().as_ref(), bidder.key().as_ref()],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    pub bidder: Signer<'info>,
+}
+
+/// Reveals the amount behind a committed bid once bidding has closed.
+#[derive(Accounts)]
+pub struct RevealBid<'info> {
     pub auction_house: Account<'info, AuctionHouse>,
     #[account(
         mut,
@@ -132,11 +691,69 @@ pub struct SettleCommittedBid<'info> {
         constraint = sealed_bid.auction == auction_house.key() @ AuctionError::BidAuctionMismatch
     )]
     pub sealed_bid: Account<'info, SealedBid>,
+    #[account(
+        mut,
+        seeds = [UNIT_BOOK_SEED, auction_house.key().as_ref()],
+        bump = unit_book.bump
+    )]
+    pub unit_book: Option<Account<'info, UnitBook>>,
+}
+
+/// Settles a batch of committed bids passed as writable `remaining_accounts`.
+#[derive(Accounts)]
+pub struct SettleCommittedBids<'info> {
+    #[account(mut)]
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        seeds = [UNIT_BOOK_SEED, auction_house.key().as_ref()],
+        bump = unit_book.bump
+    )]
+    pub unit_book: Option<Account<'info, UnitBook>>,
+}
+
+/// Reveals the sealed reserve committed at auction creation.
+#[derive(Accounts)]
+pub struct RevealReserve<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [
+            AUCTION_SEED,
+            authority.key().as_ref(),
+            &auction_house.auction_id.to_le_bytes()
+        ],
+        bump = auction_house.bump
+    )]
+    pub auction_house: Account<'info, AuctionHouse>,
+    pub authority: Signer<'info>,
 }
 
 /// Finalizes auction once all committed bids are settled.
 #[derive(Accounts)]
 pub struct FinalizeAuction<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [
+            AUCTION_SEED,
+            authority.key().as_ref(),
+            &auction_house.auction_id.to_le_bytes()
+        ],
+        bump = auction_house.bump
+    )]
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        seeds = [UNIT_BOOK_SEED, auction_house.key().as_ref()],
+        bump = unit_book.bump
+    )]
+    pub unit_book: Option<Account<'info, UnitBook>>,
+    pub authority: Signer<'info>,
+}
+
+/// Aborts an auction before it ends so bidders can reclaim deposits.
+#[derive(Accounts)]
+pub struct CancelAuction<'info> {
     #[account(
         mut,
         has_one = authority,
@@ -151,9 +768,26 @@ pub struct FinalizeAuction<'info> {
     pub authority: Signer<'info>,
 }
 
-/// Transfers winning proceeds to auction authority.
+/// Transfers winning proceeds to auction authority, less the platform fee.
+///
+/// `config` is only needed when the auction charges a fee; auctions listed
+/// before `initialize_config` ran can be claimed without. The fee goes to
+/// `fee_recipient` for lamport auctions and to `fee_recipient_token_account`
+/// for token auctions, so only the one in use has to be passed.
+///
+/// Auctions with proceeds splits pass one writable remaining account per
+/// split, in order: the recipient itself for lamport auctions, or its
+/// associated token account for the payment mint. A share the recipient can't
+/// receive goes to the authority.
 #[derive(Accounts)]
 pub struct ClaimSellerProceeds<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Option<Account<'info, ProtocolConfig>>,
+    #[account(mut)]
+    /// CHECK: Lamport fee destination, checked against the config by the handler.
+    pub fee_recipient: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub fee_recipient_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         has_one = authority,
@@ -172,10 +806,141 @@ pub struct ClaimSellerProceeds<'info> {
     )]
     /// CHECK: Vault PDA validated by seeds + bump.
     pub vault: UncheckedAccount<'info>,
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [VAULT_TOKEN_SEED, auction_house.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     #[account(mut)]
     pub authority: Signer<'info>,
 }
 
+/// Delivers the escrowed item to the winner, or back to the authority when
+/// the auction ended without a winning bid.
+#[derive(Accounts)]
+pub struct ClaimItem<'info> {
+    #[account(mut)]
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        seeds = [VAULT_SEED, auction_house.key().as_ref()],
+        bump = auction_house.vault_bump
+    )]
+    /// CHECK: Vault PDA validated by seeds + bump.
+    pub vault: UncheckedAccount<'info>,
+    #[account(address = auction_house.item_mint)]
+    pub item_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [ITEM_VAULT_SEED, auction_house.key().as_ref()],
+        bump
+    )]
+    pub item_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = item_mint,
+        token::authority = claimer,
+        token::token_program = item_token_program
+    )]
+    pub recipient_item_account: InterfaceAccount<'info, TokenAccount>,
+    pub claimer: Signer<'info>,
+    pub item_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Withdraws a sealed bid before `end_time`, returning its deposit.
+#[derive(Accounts)]
+pub struct WithdrawBid<'info> {
+    #[account(mut)]
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        has_one = bidder,
+        constraint = sealed_bid.auction == auction_house.key() @ AuctionError::BidAuctionMismatch,
+        seeds = [BID_SEED, auction_house.key().as_ref(), bidder.key().as_ref()],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, auction_house.key().as_ref()],
+        bump = auction_house.vault_bump
+    )]
+    /// CHECK: Vault PDA validated by seeds + bump.
+    pub vault: UncheckedAccount<'info>,
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [VAULT_TOKEN_SEED, auction_house.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub bidder_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+}
+
+/// Settles a multi-unit bidder: delivers the units won and refunds the
+/// deposit left after paying the clearing price for them.
+#[derive(Accounts)]
+pub struct ClaimUnits<'info> {
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        seeds = [UNIT_BOOK_SEED, auction_house.key().as_ref()],
+        bump = unit_book.bump
+    )]
+    pub unit_book: Account<'info, UnitBook>,
+    #[account(
+        mut,
+        has_one = bidder,
+        constraint = sealed_bid.auction == auction_house.key() @ AuctionError::BidAuctionMismatch,
+        seeds = [BID_SEED, auction_house.key().as_ref(), bidder.key().as_ref()],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, auction_house.key().as_ref()],
+        bump = auction_house.vault_bump
+    )]
+    /// CHECK: Vault PDA validated by seeds + bump.
+    pub vault: UncheckedAccount<'info>,
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [VAULT_TOKEN_SEED, auction_house.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub bidder_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    #[account(address = auction_house.item_mint)]
+    pub item_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [ITEM_VAULT_SEED, auction_house.key().as_ref()],
+        bump
+    )]
+    pub item_vault: InterfaceAccount<'info, TokenAccount>,
+    /// Only needed when the bidder won at least one unit.
+    #[account(
+        mut,
+        token::mint = item_mint,
+        token::authority = bidder,
+        token::token_program = item_token_program
+    )]
+    pub recipient_item_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub item_token_program: Interface<'info, TokenInterface>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+}
+
 /// Allows non-winning bidder to withdraw escrowed funds.
 #[derive(Accounts)]
 pub struct ClaimRefund<'info> {
@@ -195,6 +960,16 @@ pub struct ClaimRefund<'info> {
     )]
     /// CHECK: Vault PDA validated by seeds + bump.
     pub vault: UncheckedAccount<'info>,
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [VAULT_TOKEN_SEED, auction_house.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub bidder_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     #[account(mut)]
     pub bidder: Signer<'info>,
 }
@@ -202,6 +977,7 @@ pub struct ClaimRefund<'info> {
 /// Closes a bidder's sealed-bid account after settlement/refund safety checks.
 #[derive(Accounts)]
 pub struct CloseSealedBid<'info> {
+    #[account(mut)]
     pub auction_house: Account<'info, AuctionHouse>,
     #[account(
         mut,
@@ -209,7 +985,7 @@ pub struct CloseSealedBid<'info> {
         has_one = bidder,
         constraint = sealed_bid.auction == auction_house.key() @ AuctionError::BidAuctionMismatch,
         constraint = auction_house.finalized @ AuctionError::AuctionNotFinalized,
-        constraint = sealed_bid.bidder == auction_house.winner || sealed_bid.refund_claimed @ AuctionError::CloseNotAllowed,
+        constraint = sealed_bid.refundable_amount(&auction_house) == 0 @ AuctionError::CloseNotAllowed,
         seeds = [BID_SEED, auction_house.key().as_ref(), bidder.key().as_ref()],
         bump = sealed_bid.bump
     )]
@@ -217,3 +993,47 @@ pub struct CloseSealedBid<'info> {
     #[account(mut)]
     pub bidder: Signer<'info>,
 }
+
+/// Closes a completed auction, its vault PDA and vault token accounts,
+/// returning rent and any leftover balance to the authority.
+#[derive(Accounts)]
+pub struct CloseAuction<'info> {
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority,
+        seeds = [
+            AUCTION_SEED,
+            authority.key().as_ref(),
+            &auction_house.auction_id.to_le_bytes()
+        ],
+        bump = auction_house.bump
+    )]
+    pub auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, auction_house.key().as_ref()],
+        bump = auction_house.vault_bump
+    )]
+    /// CHECK: Vault PDA validated by seeds + bump.
+    pub vault: UncheckedAccount<'info>,
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [VAULT_TOKEN_SEED, auction_house.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [ITEM_VAULT_SEED, auction_house.key().as_ref()],
+        bump
+    )]
+    pub item_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub item_token_program: Option<Interface<'info, TokenInterface>>,
+}
@@ -23,7 +23,7 @@ pub enum AuctionError {
     BidNotCommitted,
     #[msg("Bid account has already been settled")]
     BidAlreadySettled,
-    #[msg("There are unsettled committed bids")]
+    #[msg("There are unsettled bids")]
     UnsettledCommittedBids,
     #[msg("Bid account is linked to a different auction")]
     BidAuctionMismatch,
@@ -47,4 +47,108 @@ pub enum AuctionError {
     InsufficientVaultBalance,
     #[msg("Bid account cannot be closed yet")]
     CloseNotAllowed,
+    #[msg("Bid commitment is missing")]
+    MissingCommitment,
+    #[msg("Revealed bid does not match commitment")]
+    InvalidReveal,
+    #[msg("Bid has already been revealed")]
+    BidAlreadyRevealed,
+    #[msg("Revealed bid exceeds escrowed deposit")]
+    BidExceedsDeposit,
+    #[msg("Reveal window has closed")]
+    RevealWindowClosed,
+    #[msg("Reveal window is still open")]
+    RevealWindowOpen,
+    #[msg("Unrevealed bid deposit was forfeited")]
+    BidForfeited,
+    #[msg("Token accounts are required for SPL-denominated auctions")]
+    MissingTokenAccounts,
+    #[msg("Token account does not match auction payment mint")]
+    PaymentMintMismatch,
+    #[msg("Item escrow accounts are incomplete")]
+    MissingItemAccounts,
+    #[msg("Item amount must be greater than zero")]
+    InvalidItemAmount,
+    #[msg("Auction has no escrowed item")]
+    NoEscrowedItem,
+    #[msg("Item has already been claimed")]
+    ItemAlreadyClaimed,
+    #[msg("Signer is not entitled to the escrowed item")]
+    ItemClaimNotAllowed,
+    #[msg("Auction has no sealed reserve")]
+    NoReserve,
+    #[msg("Reserve has already been revealed")]
+    ReserveAlreadyRevealed,
+    #[msg("Revealed reserve does not match commitment")]
+    InvalidReserveReveal,
+    #[msg("Reserve price was not met")]
+    ReserveNotMet,
+    #[msg("Auction has been cancelled")]
+    AuctionCancelled,
+    #[msg("Auction cannot be cancelled in its current state")]
+    CancelNotAllowed,
+    #[msg("Bid accounts are still open for this auction")]
+    OutstandingBids,
+    #[msg("Seller proceeds have not been claimed")]
+    ProceedsNotClaimed,
+    #[msg("Escrowed item has not been claimed")]
+    ItemNotClaimed,
+    #[msg("Soft-close extension settings are invalid")]
+    InvalidExtension,
+    #[msg("Auction has not started yet")]
+    AuctionNotStarted,
+    #[msg("Start time cannot be in the past")]
+    InvalidStartTime,
+    #[msg("Account is not a sealed bid PDA for this auction")]
+    InvalidBidAccount,
+    #[msg("Settlement batch is empty or too large")]
+    InvalidBatchSize,
+    #[msg("Instruction is not supported for this auction type")]
+    UnsupportedAuctionType,
+    #[msg("Dutch price schedule is invalid")]
+    InvalidDutchSchedule,
+    #[msg("Escrowed deposit does not cover the price")]
+    InsufficientDeposit,
+    #[msg("Current leader cannot withdraw their deposit")]
+    LeaderCannotWithdraw,
+    #[msg("Unit count is invalid for this auction")]
+    InvalidUnitCount,
+    #[msg("Multi-unit auctions require their unit book account")]
+    MissingUnitBook,
+    #[msg("Bid quantity is invalid")]
+    InvalidQuantity,
+    #[msg("Buy-it-now price is invalid for this auction")]
+    InvalidBuyNowPrice,
+    #[msg("Buy-it-now is not available for this auction")]
+    BuyNowUnavailable,
+    #[msg("Auction was ended by a buy-it-now purchase")]
+    AuctionBoughtOut,
+    #[msg("Withdrawal policy settings are invalid")]
+    InvalidWithdrawalPolicy,
+    #[msg("This auction does not allow bid withdrawal")]
+    WithdrawalNotAllowed,
+    #[msg("Signer is not authorized for this action")]
+    Unauthorized,
+    #[msg("Fee basis points exceed 100%")]
+    InvalidFeeBps,
+    #[msg("Fee recipient account does not match protocol config")]
+    FeeRecipientMismatch,
+    #[msg("Proceeds splits are invalid")]
+    InvalidProceedsSplits,
+    #[msg("Split recipient account is missing or does not match")]
+    SplitRecipientMismatch,
+    #[msg("Validator does not match the auction's ER validator")]
+    ValidatorMismatch,
+    #[msg("Session key settings are invalid")]
+    InvalidSession,
+    #[msg("Session key has expired")]
+    SessionExpired,
+    #[msg("Escrowed deposit exceeds the session's spend limit")]
+    SessionSpendExceeded,
+    #[msg("Protocol config and fee recipient are required to pay the fee")]
+    MissingFeeAccounts,
+    #[msg("Auction is delegated; register and fund bids before delegating it")]
+    AuctionDelegated,
+    #[msg("Fee recipient must be rent exempt")]
+    FeeRecipientNotRentExempt,
 }
@@ -1,24 +1,91 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{AuctionOutcome, AuctionType};
+
+#[event]
+pub struct ProtocolConfigUpdated {
+    pub admin: Pubkey,
+    pub fee_bps: u16,
+    pub fee_recipient: Pubkey,
+}
+
+#[event]
+pub struct SessionCreated {
+    pub bidder: Pubkey,
+    pub session_signer: Pubkey,
+    pub expires_at: i64,
+    pub max_spend: u64,
+}
+
+#[event]
+pub struct SessionRevoked {
+    pub bidder: Pubkey,
+    pub session_signer: Pubkey,
+}
+
 #[event]
 pub struct AuctionCreated {
     pub auction: Pubkey,
     pub authority: Pubkey,
     pub auction_id: u64,
+    pub auction_type: AuctionType,
+    pub payment_mint: Pubkey,
+    pub item_mint: Pubkey,
+    pub item_amount: u64,
+    pub has_reserve: bool,
     pub min_bid: u64,
     pub min_increment: u64,
+    pub starts_at: i64,
     pub ends_at: i64,
 }
 
 #[event]
 pub struct BidSubmitted {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub deposited: u64,
+}
+
+#[event]
+pub struct OpenBidPlaced {
     pub auction: Pubkey,
     pub bidder: Pubkey,
     pub amount: u64,
+    pub previous_winner: Pubkey,
+}
+
+#[event]
+pub struct EscrowDeposited {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub deposited: u64,
+}
+
+#[event]
+pub struct AuctionExtended {
+    pub auction: Pubkey,
+    pub previous_end_time: i64,
+    pub new_end_time: i64,
+}
+
+#[event]
+pub struct BidWithdrawn {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub refunded: u64,
+    pub penalty: u64,
 }
 
 #[event]
 pub struct BidCommitted {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub deposited: u64,
+}
+
+#[event]
+pub struct BidRevealed {
     pub auction: Pubkey,
     pub bidder: Pubkey,
     pub amount: u64,
@@ -33,20 +100,91 @@ pub struct BidSettled {
     pub current_winner: Pubkey,
 }
 
+#[event]
+pub struct BidForfeited {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct UnitsClaimed {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub units: u32,
+    pub item_amount: u64,
+    pub refund: u64,
+}
+
+#[event]
+pub struct ReserveRevealed {
+    pub auction: Pubkey,
+    pub reserve_price: u64,
+}
+
+#[event]
+pub struct DutchPriceAccepted {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+pub struct BoughtNow {
+    pub auction: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+}
+
 #[event]
 pub struct AuctionFinalized {
     pub auction: Pubkey,
+    pub outcome: AuctionOutcome,
     pub winner: Pubkey,
     pub final_bid: u64,
+    pub clearing_price: u64,
+}
+
+#[event]
+pub struct AuctionCancelled {
+    pub auction: Pubkey,
+    pub authority: Pubkey,
+    pub bidder_count: u32,
 }
 
 #[event]
 pub struct SellerProceedsClaimed {
     pub auction: Pubkey,
     pub authority: Pubkey,
+    /// Paid to the authority, net of the platform fee.
+    pub amount: u64,
+    pub fee: u64,
+    pub fee_recipient: Pubkey,
+}
+
+#[event]
+pub struct ProceedsDistributed {
+    pub auction: Pubkey,
+    pub recipient: Pubkey,
     pub amount: u64,
 }
 
+#[event]
+pub struct ItemClaimed {
+    pub auction: Pubkey,
+    pub recipient: Pubkey,
+    pub item_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct AuctionClosed {
+    pub auction: Pubkey,
+    pub authority: Pubkey,
+    pub swept_lamports: u64,
+    pub swept_tokens: u64,
+}
+
 #[event]
 pub struct RefundClaimed {
     pub auction: Pubkey,
@@ -1,81 +1,491 @@
-use std::str::FromStr;
-
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::TokenAccount;
 
-use crate::constants::{BID_SEED, DEVNET_ASIA_ER_VALIDATOR};
+use crate::constants::{
+    AUCTION_SEED, BID_SEED, BID_VIEWER_FLAGS, BPS_DENOMINATOR, DEVNET_ASIA_ER_VALIDATOR,
+    MAX_AUCTION_UNITS, MAX_PROCEEDS_SPLITS, MAX_SETTLE_BATCH, SESSION_SEED,
+};
 use crate::contexts::*;
 use crate::errors::AuctionError;
 use crate::events::*;
-use crate::state::BidStatus;
-use crate::utils::transfer_from_vault;
+use crate::state::{
+    AuctionHouse, AuctionOutcome, AuctionType, BidStatus, CreateAuctionParams, ProtocolConfig,
+    SealedBid, SessionKey, UnitBook, UnitBookEntry, WithdrawalPolicy,
+};
+use crate::utils::{
+    bid_commitment, close_vault_token_account, deposit_to_vault, payout_from_vault,
+    reserve_commitment, token_payment, top_up_escrow, transfer_from_vault,
+    transfer_tokens_from_vault, transfer_tokens_to_vault, unit_bid_commitment, VaultTokenAccounts,
+};
+use ephemeral_rollups_sdk::access_control::instructions::{
+    CommitAndUndelegatePermissionCpi, CommitAndUndelegatePermissionCpiAccounts,
+    CreatePermissionCpi, CreatePermissionCpiAccounts, CreatePermissionInstructionArgs,
+    DelegatePermissionCpi, DelegatePermissionCpiAccounts, UpdatePermissionCpi,
+    UpdatePermissionCpiAccounts, UpdatePermissionInstructionArgs,
+};
+use ephemeral_rollups_sdk::access_control::structs::{Member, MembersArgs};
 use ephemeral_rollups_sdk::cpi::DelegateConfig;
+use ephemeral_rollups_sdk::ephem::{commit_accounts, commit_and_undelegate_accounts};
+
+/// Creates the protocol config; the context restricts this to the program's
+/// upgrade authority, who becomes the first admin.
+pub fn initialize_config_handler(ctx: Context<InitializeConfig>, fee_bps: u16) -> Result<()> {
+    require!(fee_bps <= BPS_DENOMINATOR, AuctionError::InvalidFeeBps);
+
+    let fee_recipient = ctx.accounts.fee_recipient.key();
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.fee_bps = fee_bps;
+    config.fee_recipient = fee_recipient;
+    config.bump = ctx.bumps.config;
+
+    emit!(ProtocolConfigUpdated {
+        admin: config.admin,
+        fee_bps,
+        fee_recipient,
+    });
+
+    Ok(())
+}
+
+/// Replaces the protocol fee settings; passing a new `admin` hands over control.
+pub fn update_config_handler(
+    ctx: Context<UpdateConfig>,
+    admin: Pubkey,
+    fee_bps: u16,
+) -> Result<()> {
+    require!(fee_bps <= BPS_DENOMINATOR, AuctionError::InvalidFeeBps);
+
+    let fee_recipient = ctx.accounts.fee_recipient.key();
+    let config = &mut ctx.accounts.config;
+    config.admin = admin;
+    config.fee_bps = fee_bps;
+    config.fee_recipient = fee_recipient;
+
+    emit!(ProtocolConfigUpdated {
+        admin,
+        fee_bps,
+        fee_recipient,
+    });
+
+    Ok(())
+}
 
-/// Creates a new auction instance and initializes auction metadata.
+/// Creates a new auction instance, initializes auction metadata and escrows
+/// the item being sold when item accounts are provided.
+/// The protocol fee in force at creation is fixed on the auction.
 pub fn create_auction_handler(
     ctx: Context<CreateAuction>,
-    auction_id: u64,
-    min_bid: u64,
-    min_increment: u64,
-    duration: i64,
+    params: CreateAuctionParams,
 ) -> Result<()> {
+    let CreateAuctionParams {
+        auction_id,
+        min_bid,
+        min_increment,
+        start_time,
+        duration,
+        reveal_duration,
+        auction_type,
+        tie_break,
+        unit_count,
+        start_price,
+        price_decrement,
+        decay_interval,
+        buy_now_price,
+        withdrawal_policy,
+        withdrawal_penalty_bps,
+        item_amount,
+        reserve_commitment,
+        extension_window,
+        extension_duration,
+        max_extension,
+        er_validator,
+        commit_frequency_ms,
+        proceeds_splits,
+    } = params;
     require!(duration > 0, AuctionError::InvalidDuration);
+    require!(reveal_duration > 0, AuctionError::InvalidDuration);
     require!(min_bid > 0, AuctionError::InvalidMinBid);
+    require!(
+        extension_window >= 0 && extension_duration >= 0 && max_extension >= 0,
+        AuctionError::InvalidExtension
+    );
+    require!(
+        extension_window == 0 || (extension_duration > 0 && max_extension > 0),
+        AuctionError::InvalidExtension
+    );
+    // Soft close is for open auctions only: a sealed submit that moved
+    // `end_time` would tell everyone when bids arrive.
+    require!(
+        extension_window == 0 || auction_type == AuctionType::English,
+        AuctionError::InvalidExtension
+    );
+    if auction_type == AuctionType::Dutch {
+        require!(
+            start_price > min_bid && price_decrement > 0 && decay_interval > 0,
+            AuctionError::InvalidDutchSchedule
+        );
+        // The descending ask is the only price signal; a sealed reserve
+        // would just be a higher floor.
+        require!(
+            reserve_commitment == [0; 32],
+            AuctionError::UnsupportedAuctionType
+        );
+    }
+    require!(
+        buy_now_price == 0
+            || (buy_now_price >= min_bid
+                && matches!(
+                    auction_type,
+                    AuctionType::FirstPrice | AuctionType::SecondPrice | AuctionType::English
+                )),
+        AuctionError::InvalidBuyNowPrice
+    );
+    require!(
+        if withdrawal_policy == WithdrawalPolicy::AllowedWithPenalty {
+            withdrawal_penalty_bps > 0 && withdrawal_penalty_bps <= BPS_DENOMINATOR
+        } else {
+            withdrawal_penalty_bps == 0
+        },
+        AuctionError::InvalidWithdrawalPolicy
+    );
+    require!(
+        proceeds_splits.len() <= MAX_PROCEEDS_SPLITS
+            && proceeds_splits
+                .iter()
+                .all(|split| split.bps > 0 && split.recipient != Pubkey::default())
+            && proceeds_splits
+                .iter()
+                .map(|split| u32::from(split.bps))
+                .sum::<u32>()
+                <= u32::from(BPS_DENOMINATOR),
+        AuctionError::InvalidProceedsSplits
+    );
+    if auction_type == AuctionType::MultiUnit {
+        require!(
+            (2..=MAX_AUCTION_UNITS).contains(&unit_count),
+            AuctionError::InvalidUnitCount
+        );
+        require!(
+            ctx.accounts.unit_book.is_some(),
+            AuctionError::MissingUnitBook
+        );
+        require!(
+            ctx.accounts.item_mint.is_some(),
+            AuctionError::MissingItemAccounts
+        );
+    } else {
+        require!(unit_count == 0, AuctionError::InvalidUnitCount);
+        require!(
+            ctx.accounts.unit_book.is_none(),
+            AuctionError::UnsupportedAuctionType
+        );
+    }
+
+    let payment_mint = match &ctx.accounts.payment_mint {
+        Some(mint) => {
+            require!(
+                ctx.accounts.vault_token_account.is_some(),
+                AuctionError::MissingTokenAccounts
+            );
+            mint.key()
+        }
+        None => Pubkey::default(),
+    };
+
+    let (item_mint, item_amount) = match &ctx.accounts.item_mint {
+        Some(mint) => {
+            require!(item_amount > 0, AuctionError::InvalidItemAmount);
+            let (Some(item_vault), Some(seller_item_account), Some(item_token_program)) = (
+                &ctx.accounts.item_vault,
+                &ctx.accounts.seller_item_account,
+                &ctx.accounts.item_token_program,
+            ) else {
+                return err!(AuctionError::MissingItemAccounts);
+            };
+            let received = transfer_tokens_to_vault(
+                &ctx.accounts.authority.to_account_info(),
+                &VaultTokenAccounts {
+                    mint,
+                    vault_token_account: item_vault,
+                    user_token_account: seller_item_account,
+                    token_program: item_token_program,
+                },
+                item_amount,
+            )?;
+            (mint.key(), received)
+        }
+        None => (Pubkey::default(), 0),
+    };
+    require!(
+        item_amount >= u64::from(unit_count),
+        AuctionError::InvalidItemAmount
+    );
 
     let now = Clock::get()?.unix_timestamp;
+    let start_time = if start_time == 0 { now } else { start_time };
+    require!(start_time >= now, AuctionError::InvalidStartTime);
+
+    // The config PDA only has data once `initialize_config` has run; until
+    // then there is no fee to charge.
+    let config = &ctx.accounts.config;
+    let fee_bps = if config.data_is_empty() {
+        0
+    } else {
+        ProtocolConfig::try_deserialize(&mut &config.try_borrow_data()?[..])?.fee_bps
+    };
+
     let auction = &mut ctx.accounts.auction_house;
     auction.authority = ctx.accounts.authority.key();
     auction.auction_id = auction_id;
     auction.min_bid = min_bid;
     auction.min_increment = min_increment;
+    auction.auction_type = auction_type;
+    auction.tie_break = tie_break;
+    auction.start_price = start_price;
+    auction.price_decrement = price_decrement;
+    auction.decay_interval = decay_interval;
+    auction.buy_now_price = buy_now_price;
+    auction.withdrawal_policy = withdrawal_policy;
+    auction.withdrawal_penalty_bps = withdrawal_penalty_bps;
+    auction.payment_mint = payment_mint;
+    auction.item_mint = item_mint;
+    auction.item_amount = item_amount;
+    auction.unit_count = unit_count;
+    auction.units_sold = 0;
+    auction.reserve_commitment = reserve_commitment;
+    auction.reserve_price = 0;
+    auction.reserve_revealed = false;
     auction.highest_bid = 0;
+    auction.second_highest_bid = 0;
+    auction.clearing_price = 0;
     auction.winner = Pubkey::default();
-    auction.end_time = now + duration;
+    auction.winner_committed_at = 0;
+    auction.start_time = start_time;
+    auction.end_time = start_time
+        .checked_add(duration)
+        .ok_or(AuctionError::MathOverflow)?;
+    auction.reveal_duration = reveal_duration;
+    auction.extension_window = extension_window;
+    auction.extension_duration = extension_duration;
+    auction.er_validator = if er_validator == Pubkey::default() {
+        DEVNET_ASIA_ER_VALIDATOR
+    } else {
+        er_validator
+    };
+    auction.commit_frequency_ms = commit_frequency_ms;
+    auction.hard_end_time = auction
+        .end_time
+        .checked_add(max_extension)
+        .ok_or(AuctionError::MathOverflow)?;
     auction.bidder_count = 0;
+    auction.open_bid_count = 0;
     auction.committed_count = 0;
     auction.settled_count = 0;
+    auction.forfeited_amount = 0;
+    auction.outcome = AuctionOutcome::Pending;
     auction.finalized = false;
     auction.proceeds_claimed = false;
+    auction.item_claimed = false;
+    auction.fee_bps = fee_bps;
+    auction.proceeds_splits = proceeds_splits;
     auction.bump = ctx.bumps.auction_house;
     auction.vault_bump = ctx.bumps.vault;
 
+    if let Some(unit_book) = &mut ctx.accounts.unit_book {
+        unit_book.auction = auction.key();
+        unit_book.entries = Vec::new();
+        unit_book.bump = ctx.bumps.unit_book.ok_or(AuctionError::MissingUnitBook)?;
+    }
+
     emit!(AuctionCreated {
         auction: auction.key(),
         authority: auction.authority,
         auction_id,
+        auction_type,
+        payment_mint,
+        item_mint,
+        item_amount,
+        has_reserve: auction.has_reserve(),
         min_bid,
         min_increment,
+        starts_at: auction.start_time,
         ends_at: auction.end_time,
     });
 
     Ok(())
 }
 
+/// Registers a session key for the bidder. Until `expires_at` it can sign
+/// `submit_sealed_bid` and `commit_bid` for the bidder's bids as long as the
+/// escrow it has put behind them stays within `max_spend` in total; it can
+/// never move funds itself.
+pub fn create_session_handler(
+    ctx: Context<CreateSession>,
+    session_signer: Pubkey,
+    expires_at: i64,
+    max_spend: u64,
+) -> Result<()> {
+    let bidder = ctx.accounts.bidder.key();
+    require!(
+        session_signer != Pubkey::default() && session_signer != bidder,
+        AuctionError::InvalidSession
+    );
+    require!(
+        expires_at > Clock::get()?.unix_timestamp && max_spend > 0,
+        AuctionError::InvalidSession
+    );
+
+    let session = &mut ctx.accounts.session;
+    session.bidder = bidder;
+    session.session_signer = session_signer;
+    session.expires_at = expires_at;
+    session.max_spend = max_spend;
+    session.spent = 0;
+    session.bump = ctx.bumps.session;
+
+    emit!(SessionCreated {
+        bidder,
+        session_signer,
+        expires_at,
+        max_spend,
+    });
+
+    Ok(())
+}
+
+/// Revokes the bidder's session key. A delegated session has to come back with
+/// `undelegate_session` first, which already ends its use inside the rollup.
+pub fn revoke_session_handler(ctx: Context<RevokeSession>) -> Result<()> {
+    emit!(SessionRevoked {
+        bidder: ctx.accounts.bidder.key(),
+        session_signer: ctx.accounts.session.session_signer,
+    });
+
+    Ok(())
+}
+
+/// Delegates the bidder's session key to a rollup validator so it can sign
+/// `submit_sealed_bid` there. While delegated it can't be used on L1.
+pub fn delegate_session_handler(ctx: Context<DelegateSession>) -> Result<()> {
+    let bidder_key = ctx.accounts.bidder.key();
+    let seeds: &[&[u8]] = &[SESSION_SEED, bidder_key.as_ref()];
+    let config = DelegateConfig {
+        validator: Some(ctx.accounts.validator.key()),
+        ..Default::default()
+    };
+    ctx.accounts
+        .delegate_session(&ctx.accounts.bidder, seeds, config)?;
+
+    Ok(())
+}
+
+/// Commits a delegated session key and hands it back to L1. Sent to the
+/// rollup, it stops the key signing there straight away.
+pub fn undelegate_session_handler(ctx: Context<UndelegateSession>) -> Result<()> {
+    commit_and_undelegate_accounts(
+        &ctx.accounts.bidder.to_account_info(),
+        vec![&ctx.accounts.session.to_account_info()],
+        &ctx.accounts.magic_context.to_account_info(),
+        &ctx.accounts.magic_program.to_account_info(),
+    )?;
+
+    Ok(())
+}
+
+/// Accepts the bidder themselves, or a live session key of theirs. Returns
+/// whether the session signed.
+fn authorize_bid_signer(
+    signer: &Signer,
+    sealed_bid: &SealedBid,
+    session: Option<&SessionKey>,
+    now: i64,
+) -> Result<bool> {
+    if signer.key() == sealed_bid.bidder {
+        return Ok(false);
+    }
+    let session = session.ok_or(AuctionError::Unauthorized)?;
+    require_keys_eq!(
+        session.session_signer,
+        signer.key(),
+        AuctionError::Unauthorized
+    );
+    require!(now < session.expires_at, AuctionError::SessionExpired);
+
+    Ok(true)
+}
+
+/// Charges the session for the part of the bid's deposit it hasn't been
+/// charged for yet, keeping its running total within `max_spend`.
+fn charge_session(session: &mut SessionKey, sealed_bid: &mut SealedBid) -> Result<()> {
+    let charge = sealed_bid
+        .deposited
+        .saturating_sub(sealed_bid.session_charged);
+    let spent = session
+        .spent
+        .checked_add(charge)
+        .ok_or(AuctionError::MathOverflow)?;
+    require!(
+        spent <= session.max_spend,
+        AuctionError::SessionSpendExceeded
+    );
+    session.spent = spent;
+    sealed_bid.session_charged = sealed_bid.session_charged.max(sealed_bid.deposited);
+
+    Ok(())
+}
+
 /// Initializes sealed bid account for a bidder and links it to auction.
+/// Bidders register before an English auction is delegated to the rollup.
 pub fn initialize_sealed_bid_handler(ctx: Context<InitializeSealedBid>) -> Result<()> {
+    let auction_info = ctx.accounts.auction_house.to_account_info();
+    let mut auction = AuctionHouse::try_deserialize(&mut &auction_info.try_borrow_data()?[..])?;
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= auction.start_time, AuctionError::AuctionNotStarted);
+    require!(now < auction.end_time, AuctionError::AuctionEnded);
+    require!(!auction.finalized, AuctionError::AuctionFinalized);
+
     let sealed_bid = &mut ctx.accounts.sealed_bid;
     sealed_bid.auction = ctx.accounts.auction_house.key();
     sealed_bid.bidder = ctx.accounts.bidder.key();
+    sealed_bid.commitment = [0; 32];
     sealed_bid.amount = 0;
+    sealed_bid.quantity = 0;
     sealed_bid.deposited = 0;
     // Keep bid writable by this program in later instructions. Delegation can
     // change ownership flow, so we do not mutate status inside delegate ix.
     sealed_bid.status = BidStatus::Active;
     sealed_bid.committed = false;
+    sealed_bid.committed_at = 0;
+    sealed_bid.revealed = false;
     sealed_bid.settled = false;
     sealed_bid.refund_claimed = false;
+    sealed_bid.session_charged = 0;
     sealed_bid.bump = ctx.bumps.sealed_bid;
 
-    let auction = &mut ctx.accounts.auction_house;
     auction.bidder_count = auction
         .bidder_count
         .checked_add(1)
         .ok_or(AuctionError::MathOverflow)?;
+    auction.open_bid_count = auction
+        .open_bid_count
+        .checked_add(1)
+        .ok_or(AuctionError::MathOverflow)?;
+    auction.try_serialize(&mut &mut auction_info.try_borrow_mut_data()?[..])?;
 
     Ok(())
 }
 
 /// Delegates the bidder's sealed bid account into the PER execution domain.
+/// A sealed bid whose permission is not delegated yet fails with
+/// `CannotDelegate`.
 pub fn delegate_bid_handler(ctx: Context<DelegateBid>) -> Result<()> {
+    require!(
+        !ctx.accounts.auction_house.is_sealed_bid()
+            || ctx.accounts.permission.owner == &ephemeral_rollups_sdk::id(),
+        AuctionError::CannotDelegate
+    );
     let bidder_key = ctx.accounts.bidder.key();
     let seeds: &[&[u8]] = &[
         BID_SEED,
@@ -83,241 +493,1561 @@ pub fn delegate_bid_handler(ctx: Context<DelegateBid>) -> Result<()> {
         bidder_key.as_ref(),
     ];
 
-    ctx.accounts.delegate_sealed_bid(
-        &ctx.accounts.bidder,
-        seeds,
-        DelegateConfig {
-            validator: Pubkey::from_str(DEVNET_ASIA_ER_VALIDATOR).ok(),
-            ..Default::default()
+    let config = delegate_config(&ctx.accounts.auction_house);
+    ctx.accounts
+        .delegate_sealed_bid(&ctx.accounts.bidder, seeds, config)?;
+
+    Ok(())
+}
+
+/// Creates the PER permission for a sealed bid: inside the private rollup
+/// only the bidder and the auction authority can read the bid, its
+/// transactions and their logs. The bid PDA signs, so the program stays the
+/// only party able to change the member list.
+pub fn create_bid_permission_handler(ctx: Context<CreateBidPermission>) -> Result<()> {
+    let auction = &ctx.accounts.auction_house;
+    require!(
+        auction.is_sealed_bid(),
+        AuctionError::UnsupportedAuctionType
+    );
+    require!(
+        Clock::get()?.unix_timestamp < auction.end_time,
+        AuctionError::AuctionEnded
+    );
+
+    let sealed_bid = &ctx.accounts.sealed_bid;
+    let auction_key = auction.key();
+    let bump = [sealed_bid.bump];
+    let signer_seeds: &[&[u8]] = &[
+        BID_SEED,
+        auction_key.as_ref(),
+        sealed_bid.bidder.as_ref(),
+        &bump,
+    ];
+
+    let sealed_bid_info = sealed_bid.to_account_info();
+    let permission_info = ctx.accounts.permission.to_account_info();
+    let bidder_info = ctx.accounts.bidder.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+    CreatePermissionCpi::new(
+        &ctx.accounts.permission_program.to_account_info(),
+        CreatePermissionCpiAccounts {
+            permissioned_account: &sealed_bid_info,
+            permission: &permission_info,
+            payer: &bidder_info,
+            system_program: &system_program_info,
         },
-    )?;
+        CreatePermissionInstructionArgs {
+            args: MembersArgs {
+                members: Some(vec![
+                    Member {
+                        flags: BID_VIEWER_FLAGS,
+                        pubkey: sealed_bid.bidder,
+                    },
+                    Member {
+                        flags: BID_VIEWER_FLAGS,
+                        pubkey: auction.authority,
+                    },
+                ]),
+            },
+        },
+    )
+    .invoke_signed(&[signer_seeds])?;
+
+    Ok(())
+}
+
+/// Delegates a bid's permission to the auction's validator alongside the bid.
+pub fn delegate_bid_permission_handler(ctx: Context<DelegateBidPermission>) -> Result<()> {
+    let sealed_bid = &ctx.accounts.sealed_bid;
+    let auction_key = ctx.accounts.auction_house.key();
+    let bump = [sealed_bid.bump];
+    let signer_seeds: &[&[u8]] = &[
+        BID_SEED,
+        auction_key.as_ref(),
+        sealed_bid.bidder.as_ref(),
+        &bump,
+    ];
+
+    let permission_program_info = ctx.accounts.permission_program.to_account_info();
+    let sealed_bid_info = sealed_bid.to_account_info();
+    let bidder_info = ctx.accounts.bidder.to_account_info();
+    let permission_info = ctx.accounts.permission.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+    let buffer_info = ctx.accounts.buffer_permission.to_account_info();
+    let record_info = ctx.accounts.delegation_record_permission.to_account_info();
+    let metadata_info = ctx
+        .accounts
+        .delegation_metadata_permission
+        .to_account_info();
+    let delegation_program_info = ctx.accounts.delegation_program.to_account_info();
+    let validator_info = ctx.accounts.validator.to_account_info();
+    DelegatePermissionCpi::new(
+        &permission_program_info,
+        DelegatePermissionCpiAccounts {
+            payer: &bidder_info,
+            authority: (&bidder_info, false),
+            permissioned_account: (&sealed_bid_info, true),
+            permission: &permission_info,
+            system_program: &system_program_info,
+            owner_program: &permission_program_info,
+            delegation_buffer: &buffer_info,
+            delegation_record: &record_info,
+            delegation_metadata: &metadata_info,
+            delegation_program: &delegation_program_info,
+            validator: Some(&validator_info),
+        },
+    )
+    .invoke_signed(&[signer_seeds])?;
+
+    Ok(())
+}
+
+/// Delegates a live English auction to ER. Bidders must have registered and
+/// pre-funded their escrow through `deposit_to_escrow` beforehand: bids placed
+/// in the rollup cannot move lamports or tokens, and both instructions fail
+/// with `AuctionDelegated` from here on. Standings are committed back to L1 on
+/// the auction's commit frequency.
+pub fn delegate_auction_handler(ctx: Context<DelegateAuction>) -> Result<()> {
+    let auction = &ctx.accounts.auction_house;
+    require!(
+        auction.auction_type == AuctionType::English,
+        AuctionError::UnsupportedAuctionType
+    );
+    require!(
+        auction.outcome == AuctionOutcome::Pending,
+        AuctionError::CannotDelegate
+    );
+    require!(!auction.finalized, AuctionError::AuctionFinalized);
+    require!(
+        Clock::get()?.unix_timestamp < auction.end_time,
+        AuctionError::AuctionEnded
+    );
+
+    let authority_key = auction.authority;
+    let auction_id = auction.auction_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[AUCTION_SEED, authority_key.as_ref(), &auction_id];
+
+    let config = delegate_config(auction);
+    ctx.accounts
+        .delegate_auction_house(&ctx.accounts.authority, seeds, config)?;
 
     Ok(())
 }
 
-/// Submits/updates a bid amount and escrows additional lamports if needed.
-pub fn submit_sealed_bid_handler(ctx: Context<SubmitSealedBid>, amount: u64) -> Result<()> {
+/// Delegation settings shared by bids and auctions: the auction's validator
+/// and, when set, its commit frequency.
+fn delegate_config(auction: &AuctionHouse) -> DelegateConfig {
+    let mut config = DelegateConfig {
+        validator: Some(auction.er_validator),
+        ..Default::default()
+    };
+    if auction.commit_frequency_ms > 0 {
+        config.commit_frequency_ms = auction.commit_frequency_ms;
+    }
+    config
+}
+
+/// Stores/replaces the bid commitment against the bidder's escrow.
+///
+/// Only the hash of the bid is recorded; the deposit, funded beforehand
+/// through `deposit_to_escrow`, is public and acts as the ceiling for the
+/// amount later revealed. Nothing is transferred and the auction is only
+/// read, so this can run against a delegated bid inside the rollup.
+pub fn submit_sealed_bid_handler(
+    ctx: Context<SubmitSealedBid>,
+    commitment: [u8; 32],
+) -> Result<()> {
     let now = Clock::get()?.unix_timestamp;
     let auction = &ctx.accounts.auction_house;
+    require!(
+        auction.outcome != AuctionOutcome::Cancelled,
+        AuctionError::AuctionCancelled
+    );
+    require!(
+        auction.outcome != AuctionOutcome::BoughtNow,
+        AuctionError::AuctionBoughtOut
+    );
+    require!(
+        auction.is_sealed_bid(),
+        AuctionError::UnsupportedAuctionType
+    );
+    require!(now >= auction.start_time, AuctionError::AuctionNotStarted);
     require!(now < auction.end_time, AuctionError::AuctionEnded);
     require!(!auction.finalized, AuctionError::AuctionFinalized);
-    require!(amount >= auction.min_bid, AuctionError::BidBelowMinimum);
+    require!(commitment != [0; 32], AuctionError::MissingCommitment);
 
     let sealed_bid = &mut ctx.accounts.sealed_bid;
+    let by_session = authorize_bid_signer(
+        &ctx.accounts.signer,
+        sealed_bid,
+        ctx.accounts.session.as_deref(),
+        now,
+    )?;
     require!(
         sealed_bid.status == BidStatus::Active,
         AuctionError::AccountNotDelegated
     );
-
-    if sealed_bid.amount > 0 {
-        let required_min = sealed_bid
-            .amount
-            .checked_add(auction.min_increment)
-            .ok_or(AuctionError::MathOverflow)?;
-        require!(amount >= required_min, AuctionError::BidIncrementTooSmall);
-    }
-
-    if amount > sealed_bid.deposited {
-        let delta = amount
-            .checked_sub(sealed_bid.deposited)
-            .ok_or(AuctionError::MathOverflow)?;
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.bidder.to_account_info(),
-                    to: ctx.accounts.vault.to_account_info(),
-                },
-            ),
-            delta,
-        )?;
-        sealed_bid.deposited = amount;
+    require!(
+        sealed_bid.deposited >= auction.min_bid,
+        AuctionError::InsufficientDeposit
+    );
+    if by_session {
+        let session = ctx
+            .accounts
+            .session
+            .as_deref_mut()
+            .ok_or(AuctionError::Unauthorized)?;
+        charge_session(session, sealed_bid)?;
     }
 
-    sealed_bid.amount = amount;
+    sealed_bid.commitment = commitment;
 
     emit!(BidSubmitted {
         auction: auction.key(),
         bidder: sealed_bid.bidder,
-        amount,
+        deposited: sealed_bid.deposited,
     });
 
     Ok(())
 }
 
-/// Marks a delegated bid as committed after private execution finishes.
-pub fn commit_bid_handler(ctx: Context<CommitBid>) -> Result<()> {
+/// Places a public English bid that must beat the current leader by
+/// `min_increment`. The leader changes immediately, so the outbid bidder can
+/// pull their deposit through `claim_refund` without waiting for settlement.
+pub fn place_open_bid_handler(ctx: Context<PlaceOpenBid>, amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
     let auction = &mut ctx.accounts.auction_house;
-    require!(
-        ctx.accounts.sealed_bid.status == BidStatus::Active,
-        AuctionError::AccountNotDelegated
-    );
-    require!(
-        ctx.accounts.sealed_bid.amount >= auction.min_bid,
-        AuctionError::BidBelowMinimum
-    );
+    check_open_bid(auction, amount, now)?;
 
     let sealed_bid = &mut ctx.accounts.sealed_bid;
-    sealed_bid.status = BidStatus::Committed;
-
-    if !sealed_bid.committed {
-        sealed_bid.committed = true;
-        auction.committed_count = auction
-            .committed_count
-            .checked_add(1)
-            .ok_or(AuctionError::MathOverflow)?;
+    // A bidder who already withdrew after being outbid starts from an empty
+    // escrow again.
+    if sealed_bid.refund_claimed {
+        sealed_bid.deposited = 0;
+        sealed_bid.refund_claimed = false;
     }
+    top_up_escrow(
+        sealed_bid,
+        amount,
+        &ctx.accounts.bidder.to_account_info(),
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        || {
+            token_payment(
+                auction,
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.vault_token_account,
+                &ctx.accounts.bidder_token_account,
+                &ctx.accounts.token_program,
+            )
+        },
+    )?;
 
-    emit!(BidCommitted {
-        auction: auction.key(),
-        bidder: sealed_bid.bidder,
-        amount: sealed_bid.amount,
-    });
+    sealed_bid.amount = amount;
+    let auction_key = auction.key();
+    apply_open_bid(auction, &auction_key, sealed_bid.bidder, amount, now);
 
     Ok(())
 }
 
-/// L1-compatible commit path for regular devnet/localnet testing.
-pub fn commit_bid_l1_handler(ctx: Context<CommitBidL1>) -> Result<()> {
-    let auction = &mut ctx.accounts.auction_house;
+/// Credits the bidder's escrow ahead of bidding, so bids placed inside the
+/// rollup never need a transfer. Runs on L1 while the bid account (and, for
+/// English auctions, the auction) is not delegated; for SPL auctions the
+/// credit is what the vault received, net of any Token-2022 transfer fee.
+pub fn deposit_to_escrow_handler(ctx: Context<DepositToEscrow>, amount: u64) -> Result<()> {
+    let auction_key = ctx.accounts.auction_house.key();
+    let auction =
+        &AuctionHouse::try_deserialize(&mut &ctx.accounts.auction_house.try_borrow_data()?[..])?;
     require!(
-        ctx.accounts.sealed_bid.status == BidStatus::Active,
-        AuctionError::AccountNotDelegated
+        auction.is_sealed_bid() || auction.auction_type == AuctionType::English,
+        AuctionError::UnsupportedAuctionType
     );
     require!(
-        ctx.accounts.sealed_bid.amount >= auction.min_bid,
-        AuctionError::BidBelowMinimum
+        auction.outcome != AuctionOutcome::Cancelled,
+        AuctionError::AuctionCancelled
+    );
+    require!(!auction.finalized, AuctionError::AuctionFinalized);
+    require!(
+        Clock::get()?.unix_timestamp < auction.end_time,
+        AuctionError::AuctionEnded
     );
 
     let sealed_bid = &mut ctx.accounts.sealed_bid;
-    sealed_bid.status = BidStatus::Committed;
-
-    if !sealed_bid.committed {
-        sealed_bid.committed = true;
-        auction.committed_count = auction
-            .committed_count
-            .checked_add(1)
-            .ok_or(AuctionError::MathOverflow)?;
+    if sealed_bid.refund_claimed {
+        sealed_bid.deposited = 0;
+        sealed_bid.refund_claimed = false;
     }
+    let payment = token_payment(
+        auction,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.bidder_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    let received = deposit_to_vault(
+        &ctx.accounts.bidder.to_account_info(),
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        payment,
+        amount,
+    )?;
+    sealed_bid.deposited = sealed_bid
+        .deposited
+        .checked_add(received)
+        .ok_or(AuctionError::MathOverflow)?;
 
-    emit!(BidCommitted {
-        auction: auction.key(),
+    emit!(EscrowDeposited {
+        auction: auction_key,
         bidder: sealed_bid.bidder,
-        amount: sealed_bid.amount,
+        amount: received,
+        deposited: sealed_bid.deposited,
     });
 
     Ok(())
 }
 
-/// Settles one committed bid into global winner/highest-bid state.
-pub fn settle_committed_bid_handler(ctx: Context<SettleCommittedBid>) -> Result<()> {
+/// Places an English bid entirely out of the bidder's pre-funded escrow. The
+/// bid account is only read, which lets a delegated auction take bids inside
+/// the rollup while deposits stay on L1.
+pub fn place_escrowed_bid_handler(ctx: Context<PlaceEscrowedBid>, amount: u64) -> Result<()> {
     let now = Clock::get()?.unix_timestamp;
     let auction = &mut ctx.accounts.auction_house;
-    require!(now >= auction.end_time, AuctionError::AuctionActive);
-    require!(!auction.finalized, AuctionError::AuctionFinalized);
+    check_open_bid(auction, amount, now)?;
 
-    let sealed_bid = &mut ctx.accounts.sealed_bid;
+    let sealed_bid = &ctx.accounts.sealed_bid;
     require!(
-        sealed_bid.status == BidStatus::Committed && sealed_bid.committed,
-        AuctionError::BidNotCommitted
+        !sealed_bid.refund_claimed && sealed_bid.deposited >= amount,
+        AuctionError::InsufficientDeposit
     );
-    require!(!sealed_bid.settled, AuctionError::BidAlreadySettled);
-
-    if sealed_bid.amount > auction.highest_bid {
-        auction.highest_bid = sealed_bid.amount;
-        auction.winner = sealed_bid.bidder;
-    }
-
-    sealed_bid.settled = true;
-    auction.settled_count = auction
-        .settled_count
-        .checked_add(1)
-        .ok_or(AuctionError::MathOverflow)?;
 
-    emit!(BidSettled {
-        auction: auction.key(),
-        bidder: sealed_bid.bidder,
-        amount: sealed_bid.amount,
-        current_highest_bid: auction.highest_bid,
-        current_winner: auction.winner,
-    });
+    let auction_key = auction.key();
+    apply_open_bid(auction, &auction_key, sealed_bid.bidder, amount, now);
 
     Ok(())
 }
 
-/// Locks final winner once all committed bids have been settled.
-pub fn finalize_auction_handler(ctx: Context<FinalizeAuction>) -> Result<()> {
-    let auction = &mut ctx.accounts.auction_house;
+/// Checks that an English auction is taking bids and `amount` beats the
+/// current leader.
+fn check_open_bid(auction: &AuctionHouse, amount: u64, now: i64) -> Result<()> {
     require!(
-        Clock::get()?.unix_timestamp >= auction.end_time,
-        AuctionError::AuctionActive
+        auction.auction_type == AuctionType::English,
+        AuctionError::UnsupportedAuctionType
+    );
+    require!(
+        auction.outcome != AuctionOutcome::Cancelled,
+        AuctionError::AuctionCancelled
+    );
+    require!(
+        auction.outcome != AuctionOutcome::BoughtNow,
+        AuctionError::AuctionBoughtOut
     );
+    require!(now >= auction.start_time, AuctionError::AuctionNotStarted);
+    require!(now < auction.end_time, AuctionError::AuctionEnded);
     require!(!auction.finalized, AuctionError::AuctionFinalized);
+    require!(amount >= auction.min_bid, AuctionError::BidBelowMinimum);
     require!(
-        auction.settled_count == auction.committed_count,
-        AuctionError::UnsettledCommittedBids
+        amount >= auction.next_open_bid_minimum(),
+        AuctionError::BidIncrementTooSmall
     );
 
-    auction.finalized = true;
+    Ok(())
+}
 
-    emit!(AuctionFinalized {
-        auction: auction.key(),
-        winner: auction.winner,
-        final_bid: auction.highest_bid,
+/// Makes `bidder` the English leader at `amount` and applies soft close.
+fn apply_open_bid(
+    auction: &mut AuctionHouse,
+    auction_key: &Pubkey,
+    bidder: Pubkey,
+    amount: u64,
+    now: i64,
+) {
+    let previous_winner = auction.winner;
+    auction.second_highest_bid = auction.highest_bid;
+    auction.highest_bid = amount;
+    auction.winner = bidder;
+    auction.winner_committed_at = now;
+
+    emit!(OpenBidPlaced {
+        auction: *auction_key,
+        bidder,
+        amount,
+        previous_winner,
     });
 
-    Ok(())
+    if let Some(new_end_time) = auction.soft_close_extension(now) {
+        let previous_end_time = auction.end_time;
+        auction.end_time = new_end_time;
+
+        emit!(AuctionExtended {
+            auction: *auction_key,
+            previous_end_time,
+            new_end_time,
+        });
+    }
 }
 
-/// Allows seller to withdraw winning proceeds from the escrow vault.
-pub fn claim_seller_proceeds_handler(ctx: Context<ClaimSellerProceeds>) -> Result<()> {
+/// Accepts the current Dutch ask. The bidder's escrow is topped up to the
+/// price, the bidder becomes the winner and the auction finalizes at once;
+/// the seller then claims through `claim_seller_proceeds`.
+pub fn accept_dutch_price_handler(ctx: Context<AcceptDutchPrice>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
     let auction = &mut ctx.accounts.auction_house;
-    require!(auction.finalized, AuctionError::AuctionNotFinalized);
+    require!(
+        auction.auction_type == AuctionType::Dutch,
+        AuctionError::UnsupportedAuctionType
+    );
+    require!(
+        auction.outcome != AuctionOutcome::Cancelled,
+        AuctionError::AuctionCancelled
+    );
+    require!(now >= auction.start_time, AuctionError::AuctionNotStarted);
+    require!(now < auction.end_time, AuctionError::AuctionEnded);
+    require!(!auction.finalized, AuctionError::AuctionFinalized);
+
+    let price = auction.dutch_price(now);
+    let sealed_bid = &mut ctx.accounts.sealed_bid;
+    top_up_escrow(
+        sealed_bid,
+        price,
+        &ctx.accounts.bidder.to_account_info(),
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        || {
+            token_payment(
+                auction,
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.vault_token_account,
+                &ctx.accounts.bidder_token_account,
+                &ctx.accounts.token_program,
+            )
+        },
+    )?;
+
+    sealed_bid.amount = price;
+    auction.highest_bid = price;
+    auction.winner = sealed_bid.bidder;
+    auction.winner_committed_at = now;
+    auction.clearing_price = price;
+    auction.outcome = AuctionOutcome::Sold;
+    auction.finalized = true;
+
+    emit!(DutchPriceAccepted {
+        auction: auction.key(),
+        bidder: sealed_bid.bidder,
+        price,
+    });
+    emit!(AuctionFinalized {
+        auction: auction.key(),
+        outcome: auction.outcome,
+        winner: auction.winner,
+        final_bid: price,
+        clearing_price: price,
+    });
+
+    Ok(())
+}
+
+/// Ends the auction at the buy-it-now price. The buyer's escrow is topped up
+/// to the price and the auction finalizes with the buyer as winner; every
+/// other bidder refunds through `claim_refund`. In English auctions the
+/// option lapses once the open bidding reaches the price.
+pub fn buy_now_handler(ctx: Context<BuyNow>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let auction = &mut ctx.accounts.auction_house;
+    require!(
+        auction.outcome != AuctionOutcome::Cancelled,
+        AuctionError::AuctionCancelled
+    );
+    require!(
+        auction.outcome != AuctionOutcome::BoughtNow,
+        AuctionError::AuctionBoughtOut
+    );
+    require!(now >= auction.start_time, AuctionError::AuctionNotStarted);
+    require!(now < auction.end_time, AuctionError::AuctionEnded);
+    require!(!auction.finalized, AuctionError::AuctionFinalized);
+    let price = auction.buy_now_price;
+    require!(
+        price > 0 && auction.highest_bid < price,
+        AuctionError::BuyNowUnavailable
+    );
+
+    let sealed_bid = &mut ctx.accounts.sealed_bid;
+    top_up_escrow(
+        sealed_bid,
+        price,
+        &ctx.accounts.bidder.to_account_info(),
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        || {
+            token_payment(
+                auction,
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.vault_token_account,
+                &ctx.accounts.bidder_token_account,
+                &ctx.accounts.token_program,
+            )
+        },
+    )?;
+
+    sealed_bid.amount = price;
+    auction.second_highest_bid = auction.highest_bid;
+    auction.highest_bid = price;
+    auction.winner = sealed_bid.bidder;
+    auction.winner_committed_at = now;
+    auction.clearing_price = price;
+    auction.outcome = AuctionOutcome::BoughtNow;
+    auction.finalized = true;
+
+    emit!(BoughtNow {
+        auction: auction.key(),
+        buyer: sealed_bid.bidder,
+        price,
+    });
+    emit!(AuctionFinalized {
+        auction: auction.key(),
+        outcome: auction.outcome,
+        winner: auction.winner,
+        final_bid: price,
+        clearing_price: price,
+    });
+
+    Ok(())
+}
+
+/// Marks a delegated bid as committed after private execution finishes.
+pub fn commit_bid_handler(ctx: Context<CommitBid>) -> Result<()> {
+    authorize_bid_signer(
+        &ctx.accounts.signer,
+        &ctx.accounts.sealed_bid,
+        ctx.accounts.session.as_deref(),
+        Clock::get()?.unix_timestamp,
+    )?;
+    mark_bid_committed(&ctx.accounts.auction_house, &mut ctx.accounts.sealed_bid)
+}
+
+/// Marks a delegated bid as committed, then commits it and hands ownership
+/// back to this program on L1. The bid is written out before the magic
+/// program snapshots it, so a sealed bid lands on L1 in `Committed` state.
+/// The undelegation callback itself is the `process_undelegation`
+/// instruction generated by `#[ephemeral]`.
+///
+/// A bid with no commitment, or one returned once bidding closed, is
+/// undelegated as is; only the bidder can turn it into a committed bid. Either
+/// way `claim_refund` and `close_sealed_bid` can reach it on L1.
+pub fn commit_and_undelegate_bid_handler(ctx: Context<CommitAndUndelegateBid>) -> Result<()> {
+    let auction = &ctx.accounts.auction_house;
+    let now = Clock::get()?.unix_timestamp;
+    let is_bidder = ctx.accounts.payer.key() == ctx.accounts.sealed_bid.bidder;
+    require!(
+        is_bidder || auction.finalized || now >= auction.end_time,
+        AuctionError::Unauthorized
+    );
+    let sealed_bid = &mut ctx.accounts.sealed_bid;
+    if is_bidder
+        && sealed_bid.status == BidStatus::Active
+        && sealed_bid.commitment != [0; 32]
+        && now < auction.end_time
+    {
+        mark_bid_committed(auction, sealed_bid)?;
+    }
+    sealed_bid.exit(&crate::ID)?;
+
+    commit_and_undelegate_accounts(
+        &ctx.accounts.payer.to_account_info(),
+        vec![&ctx.accounts.sealed_bid.to_account_info()],
+        &ctx.accounts.magic_context.to_account_info(),
+        &ctx.accounts.magic_program.to_account_info(),
+    )?;
+
+    Ok(())
+}
+
+/// Commits a delegated auction's current standings to L1. The delegation
+/// already commits on the auction's schedule; this forces one in between.
+pub fn commit_auction_handler(ctx: Context<CommitAuction>) -> Result<()> {
+    commit_accounts(
+        &ctx.accounts.payer.to_account_info(),
+        vec![&ctx.accounts.auction_house.to_account_info()],
+        &ctx.accounts.magic_context.to_account_info(),
+        &ctx.accounts.magic_program.to_account_info(),
+    )?;
+
+    Ok(())
+}
+
+/// Commits a delegated auction and hands it back to L1, where it has to be
+/// before `finalize_auction`. Anyone can do this once bidding has closed so
+/// finalization does not depend on the authority.
+pub fn undelegate_auction_handler(ctx: Context<UndelegateAuction>) -> Result<()> {
+    let auction = &ctx.accounts.auction_house;
+    require!(
+        ctx.accounts.payer.key() == auction.authority
+            || Clock::get()?.unix_timestamp >= auction.end_time,
+        AuctionError::Unauthorized
+    );
+
+    commit_and_undelegate_accounts(
+        &ctx.accounts.payer.to_account_info(),
+        vec![&ctx.accounts.auction_house.to_account_info()],
+        &ctx.accounts.magic_context.to_account_info(),
+        &ctx.accounts.magic_program.to_account_info(),
+    )?;
+
+    Ok(())
+}
+
+/// Makes a sealed bid public once bidding has closed, by clearing its
+/// permission's member list, and hands the permission back to L1. Anyone can
+/// call this after `end_time`, so reveals never wait on the bidder.
+pub fn release_bid_permission_handler(ctx: Context<ReleaseBidPermission>) -> Result<()> {
+    let auction = &ctx.accounts.auction_house;
+    require!(
+        auction.finalized || Clock::get()?.unix_timestamp >= auction.end_time,
+        AuctionError::AuctionActive
+    );
+
+    let sealed_bid = &ctx.accounts.sealed_bid;
+    let auction_key = auction.key();
+    let bump = [sealed_bid.bump];
+    let signer_seeds: &[&[u8]] = &[
+        BID_SEED,
+        auction_key.as_ref(),
+        sealed_bid.bidder.as_ref(),
+        &bump,
+    ];
+
+    let permission_program_info = ctx.accounts.permission_program.to_account_info();
+    let payer_info = ctx.accounts.payer.to_account_info();
+    let sealed_bid_info = sealed_bid.to_account_info();
+    let permission_info = ctx.accounts.permission.to_account_info();
+    UpdatePermissionCpi::new(
+        &permission_program_info,
+        UpdatePermissionCpiAccounts {
+            authority: (&payer_info, false),
+            permissioned_account: (&sealed_bid_info, true),
+            permission: &permission_info,
+        },
+        UpdatePermissionInstructionArgs {
+            args: MembersArgs { members: None },
+        },
+    )
+    .invoke_signed(&[signer_seeds])?;
+
+    let magic_program_info = ctx.accounts.magic_program.to_account_info();
+    let magic_context_info = ctx.accounts.magic_context.to_account_info();
+    CommitAndUndelegatePermissionCpi::new(
+        &permission_program_info,
+        CommitAndUndelegatePermissionCpiAccounts {
+            authority: (&payer_info, false),
+            permissioned_account: (&sealed_bid_info, true),
+            permission: &permission_info,
+            magic_program: &magic_program_info,
+            magic_context: &magic_context_info,
+        },
+    )
+    .invoke_signed(&[signer_seeds])?;
+
+    Ok(())
+}
+
+/// L1-compatible commit path for regular devnet/localnet testing.
+pub fn commit_bid_l1_handler(ctx: Context<CommitBidL1>) -> Result<()> {
+    mark_bid_committed(&ctx.accounts.auction_house, &mut ctx.accounts.sealed_bid)
+}
+
+/// Shared commit step: locks the bid and stamps its first commit time. It
+/// writes only the bid, which is the one account delegated to the rollup;
+/// the auction's counters are aggregated on L1 at settlement.
+fn mark_bid_committed(auction: &AuctionHouse, sealed_bid: &mut SealedBid) -> Result<()> {
+    require!(
+        sealed_bid.status == BidStatus::Active,
+        AuctionError::AccountNotDelegated
+    );
+    require!(!sealed_bid.settled, AuctionError::BidAlreadySettled);
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < auction.end_time, AuctionError::AuctionEnded);
+    require!(
+        sealed_bid.commitment != [0; 32],
+        AuctionError::MissingCommitment
+    );
+    require!(
+        sealed_bid.deposited >= auction.min_bid,
+        AuctionError::BidBelowMinimum
+    );
+
+    sealed_bid.status = BidStatus::Committed;
+
+    if !sealed_bid.committed {
+        sealed_bid.committed = true;
+        sealed_bid.committed_at = now;
+    }
+
+    emit!(BidCommitted {
+        auction: sealed_bid.auction,
+        bidder: sealed_bid.bidder,
+        deposited: sealed_bid.deposited,
+    });
+
+    Ok(())
+}
+
+/// Pulls a sealed bid before `end_time` when the auction's withdrawal policy
+/// permits it. The deposit, less any penalty kept in the vault for the
+/// seller, goes back to the bidder, and a committed bid goes back to
+/// uncommitted.
+pub fn withdraw_bid_handler(ctx: Context<WithdrawBid>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let auction_key = ctx.accounts.auction_house.key();
+    let auction = &mut ctx.accounts.auction_house;
+    require!(
+        auction.withdrawal_policy != WithdrawalPolicy::Forbidden,
+        AuctionError::WithdrawalNotAllowed
+    );
+    require!(
+        auction.is_sealed_bid(),
+        AuctionError::UnsupportedAuctionType
+    );
+    require!(now < auction.end_time, AuctionError::AuctionEnded);
+    require!(!auction.finalized, AuctionError::AuctionFinalized);
+
+    let sealed_bid = &mut ctx.accounts.sealed_bid;
+    require!(sealed_bid.deposited > 0, AuctionError::NoRefundAvailable);
+
+    let penalty = if auction.withdrawal_policy == WithdrawalPolicy::AllowedWithPenalty {
+        let penalty = u128::from(sealed_bid.deposited)
+            .checked_mul(u128::from(auction.withdrawal_penalty_bps))
+            .ok_or(AuctionError::MathOverflow)?
+            / u128::from(BPS_DENOMINATOR);
+        u64::try_from(penalty).map_err(|_| error!(AuctionError::MathOverflow))?
+    } else {
+        0
+    };
+    let refunded = sealed_bid
+        .deposited
+        .checked_sub(penalty)
+        .ok_or(AuctionError::MathOverflow)?;
+
+    auction.forfeited_amount = auction
+        .forfeited_amount
+        .checked_add(penalty)
+        .ok_or(AuctionError::MathOverflow)?;
+
+    sealed_bid.commitment = [0; 32];
+    sealed_bid.amount = 0;
+    sealed_bid.quantity = 0;
+    sealed_bid.deposited = 0;
+    sealed_bid.status = BidStatus::Active;
+    sealed_bid.committed = false;
+    sealed_bid.committed_at = 0;
+
+    if refunded > 0 {
+        let payment = token_payment(
+            auction,
+            &ctx.accounts.payment_mint,
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.bidder_token_account,
+            &ctx.accounts.token_program,
+        )?;
+        payout_from_vault(
+            &auction_key,
+            auction,
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.bidder.to_account_info(),
+            payment,
+            refunded,
+        )?;
+    }
+
+    emit!(BidWithdrawn {
+        auction: auction_key,
+        bidder: sealed_bid.bidder,
+        refunded,
+        penalty,
+    });
+
+    Ok(())
+}
+
+/// Reveals a committed bid by checking the (amount, salt, bidder) preimage.
+pub fn reveal_bid_handler(ctx: Context<RevealBid>, amount: u64, salt: [u8; 32]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let auction = &ctx.accounts.auction_house;
+    require!(now >= auction.end_time, AuctionError::AuctionActive);
+    require!(
+        now < auction.reveal_end_time(),
+        AuctionError::RevealWindowClosed
+    );
+    require!(!auction.finalized, AuctionError::AuctionFinalized);
+    require!(
+        auction.auction_type != AuctionType::MultiUnit,
+        AuctionError::UnsupportedAuctionType
+    );
+
+    let sealed_bid = &mut ctx.accounts.sealed_bid;
+    require!(
+        sealed_bid.status == BidStatus::Committed && sealed_bid.committed,
+        AuctionError::BidNotCommitted
+    );
+    require!(!sealed_bid.revealed, AuctionError::BidAlreadyRevealed);
+    require!(
+        bid_commitment(amount, &salt, &sealed_bid.bidder) == sealed_bid.commitment,
+        AuctionError::InvalidReveal
+    );
+    require!(amount >= auction.min_bid, AuctionError::BidBelowMinimum);
+    require!(
+        amount <= sealed_bid.deposited,
+        AuctionError::BidExceedsDeposit
+    );
+
+    sealed_bid.amount = amount;
+    sealed_bid.revealed = true;
+
+    emit!(BidRevealed {
+        auction: auction.key(),
+        bidder: sealed_bid.bidder,
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Reveals a multi-unit bid: a unit price and a quantity of lots, whose total
+/// must fit within the escrowed deposit.
+pub fn reveal_unit_bid_handler(
+    ctx: Context<RevealBid>,
+    unit_price: u64,
+    quantity: u32,
+    salt: [u8; 32],
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let auction = &ctx.accounts.auction_house;
+    require!(now >= auction.end_time, AuctionError::AuctionActive);
+    require!(
+        now < auction.reveal_end_time(),
+        AuctionError::RevealWindowClosed
+    );
+    require!(!auction.finalized, AuctionError::AuctionFinalized);
+    require!(
+        auction.auction_type == AuctionType::MultiUnit,
+        AuctionError::UnsupportedAuctionType
+    );
+
+    let sealed_bid = &mut ctx.accounts.sealed_bid;
+    require!(
+        sealed_bid.status == BidStatus::Committed && sealed_bid.committed,
+        AuctionError::BidNotCommitted
+    );
+    require!(!sealed_bid.revealed, AuctionError::BidAlreadyRevealed);
+    require!(
+        unit_bid_commitment(unit_price, quantity, &salt, &sealed_bid.bidder)
+            == sealed_bid.commitment,
+        AuctionError::InvalidReveal
+    );
+    require!(unit_price >= auction.min_bid, AuctionError::BidBelowMinimum);
+    require!(
+        quantity > 0 && quantity <= auction.unit_count,
+        AuctionError::InvalidQuantity
+    );
+    let total = unit_price
+        .checked_mul(u64::from(quantity))
+        .ok_or(AuctionError::MathOverflow)?;
+    require!(
+        total <= sealed_bid.deposited,
+        AuctionError::BidExceedsDeposit
+    );
+
+    sealed_bid.amount = unit_price;
+    sealed_bid.quantity = quantity;
+    sealed_bid.revealed = true;
+
+    emit!(BidRevealed {
+        auction: auction.key(),
+        bidder: sealed_bid.bidder,
+        amount: unit_price,
+    });
+
+    Ok(())
+}
+
+/// Settles one committed bid into global winner/highest-bid state.
+pub fn settle_committed_bid_handler(ctx: Context<SettleCommittedBid>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let auction = &mut ctx.accounts.auction_house;
+    require!(
+        auction.outcome != AuctionOutcome::Cancelled,
+        AuctionError::AuctionCancelled
+    );
+    require!(now >= auction.end_time, AuctionError::AuctionActive);
+    require!(!auction.finalized, AuctionError::AuctionFinalized);
+
+    let auction_key = auction.key();
+    settle_bid(
+        auction,
+        &auction_key,
+        &mut ctx.accounts.sealed_bid,
+        ctx.accounts.unit_book.as_deref_mut(),
+        now,
+    )
+}
+
+/// Settles up to `MAX_SETTLE_BATCH` committed bids passed as writable
+/// `remaining_accounts`. Each account must be this auction's bid PDA;
+/// already-settled bids are skipped so overlapping crank batches are safe.
+/// Standings are folded in memory and the auction is written back once.
+pub fn settle_committed_bids_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleCommittedBids<'info>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let auction_key = ctx.accounts.auction_house.key();
+    let auction = &mut ctx.accounts.auction_house;
+    require!(
+        auction.outcome != AuctionOutcome::Cancelled,
+        AuctionError::AuctionCancelled
+    );
+    require!(now >= auction.end_time, AuctionError::AuctionActive);
+    require!(!auction.finalized, AuctionError::AuctionFinalized);
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() <= MAX_SETTLE_BATCH,
+        AuctionError::InvalidBatchSize
+    );
+
+    let mut unit_book = ctx.accounts.unit_book.as_deref_mut();
+    for bid_info in ctx.remaining_accounts {
+        require!(bid_info.is_writable, AuctionError::InvalidBidAccount);
+        let mut sealed_bid = Account::<SealedBid>::try_from(bid_info)?;
+        require_keys_eq!(
+            sealed_bid.auction,
+            auction_key,
+            AuctionError::BidAuctionMismatch
+        );
+        let expected_key = Pubkey::create_program_address(
+            &[
+                BID_SEED,
+                auction_key.as_ref(),
+                sealed_bid.bidder.as_ref(),
+                &[sealed_bid.bump],
+            ],
+            ctx.program_id,
+        )
+        .map_err(|_| error!(AuctionError::InvalidBidAccount))?;
+        require_keys_eq!(
+            expected_key,
+            bid_info.key(),
+            AuctionError::InvalidBidAccount
+        );
+
+        if sealed_bid.settled {
+            continue;
+        }
+
+        settle_bid(
+            auction,
+            &auction_key,
+            &mut sealed_bid,
+            unit_book.as_deref_mut(),
+            now,
+        )?;
+        // Persist immediately so a duplicate entry later in the batch is skipped.
+        sealed_bid.exit(ctx.program_id)?;
+    }
+
+    Ok(())
+}
+
+/// Shared settlement step for one bid account. Callers have already checked
+/// that bidding is over and the auction is not finalized.
+///
+/// Revealed bids compete for the win. Committed bids still unrevealed once the
+/// reveal window closes are settled as forfeited and their deposit goes to the
+/// seller. Bids that were never committed are only counted and refund in full.
+fn settle_bid(
+    auction: &mut AuctionHouse,
+    auction_key: &Pubkey,
+    sealed_bid: &mut SealedBid,
+    unit_book: Option<&mut UnitBook>,
+    now: i64,
+) -> Result<()> {
+    require!(!sealed_bid.settled, AuctionError::BidAlreadySettled);
+
+    if !sealed_bid.committed {
+        // Never entered the auction.
+    } else if sealed_bid.revealed && auction.auction_type == AuctionType::MultiUnit {
+        let unit_book = unit_book.ok_or(AuctionError::MissingUnitBook)?;
+        unit_book.insert(
+            auction.unit_count,
+            auction.tie_break,
+            UnitBookEntry {
+                bidder: sealed_bid.bidder,
+                unit_price: sealed_bid.amount,
+                quantity: sealed_bid.quantity,
+                committed_at: sealed_bid.committed_at,
+            },
+        );
+        auction.highest_bid = auction.highest_bid.max(sealed_bid.amount);
+    } else if sealed_bid.revealed {
+        auction.record_revealed_bid(
+            sealed_bid.bidder,
+            sealed_bid.amount,
+            sealed_bid.committed_at,
+        );
+    } else {
+        require!(
+            now >= auction.reveal_end_time(),
+            AuctionError::RevealWindowOpen
+        );
+        auction.forfeited_amount = auction
+            .forfeited_amount
+            .checked_add(sealed_bid.deposited)
+            .ok_or(AuctionError::MathOverflow)?;
+
+        emit!(BidForfeited {
+            auction: *auction_key,
+            bidder: sealed_bid.bidder,
+            amount: sealed_bid.deposited,
+        });
+    }
+
+    if sealed_bid.committed {
+        auction.committed_count = auction
+            .committed_count
+            .checked_add(1)
+            .ok_or(AuctionError::MathOverflow)?;
+    }
+    sealed_bid.settled = true;
+    auction.settled_count = auction
+        .settled_count
+        .checked_add(1)
+        .ok_or(AuctionError::MathOverflow)?;
+
+    emit!(BidSettled {
+        auction: *auction_key,
+        bidder: sealed_bid.bidder,
+        amount: sealed_bid.amount,
+        current_highest_bid: auction.highest_bid,
+        current_winner: auction.winner,
+    });
+
+    Ok(())
+}
+
+/// Reveals the sealed reserve price. Like bids, it has to be revealed before
+/// the reveal window closes; a reserve left sealed counts as not met.
+pub fn reveal_reserve_handler(
+    ctx: Context<RevealReserve>,
+    reserve_price: u64,
+    salt: [u8; 32],
+) -> Result<()> {
+    let auction_key = ctx.accounts.auction_house.key();
+    let auction = &mut ctx.accounts.auction_house;
+    require!(!auction.finalized, AuctionError::AuctionFinalized);
+    require!(
+        Clock::get()?.unix_timestamp < auction.reveal_end_time(),
+        AuctionError::RevealWindowClosed
+    );
+    require!(auction.has_reserve(), AuctionError::NoReserve);
+    require!(
+        !auction.reserve_revealed,
+        AuctionError::ReserveAlreadyRevealed
+    );
+    require!(
+        reserve_commitment(reserve_price, &salt, &auction_key) == auction.reserve_commitment,
+        AuctionError::InvalidReserveReveal
+    );
+
+    auction.reserve_price = reserve_price;
+    auction.reserve_revealed = true;
+
+    emit!(ReserveRevealed {
+        auction: auction_key,
+        reserve_price,
+    });
+
+    Ok(())
+}
+
+/// Locks final winner once all committed bids have been settled.
+///
+/// A highest bid below the revealed reserve, or any bid against a reserve the
+/// authority never revealed, finalizes as `ReserveNotMet`: the winner is
+/// cleared so every bidder, including the would-be winner, refunds in full
+/// and the seller has no proceeds to claim.
+/// A sale with a reserve clears at no less than the revealed reserve.
+pub fn finalize_auction_handler(ctx: Context<FinalizeAuction>) -> Result<()> {
+    let auction = &mut ctx.accounts.auction_house;
+    require!(
+        Clock::get()?.unix_timestamp >= auction.reveal_end_time(),
+        AuctionError::RevealWindowOpen
+    );
+    require!(!auction.finalized, AuctionError::AuctionFinalized);
+    // Commits happen on the rollup and are only counted at settlement, so
+    // every bid account has to pass through settlement before finalizing.
+    require!(
+        !auction.is_sealed_bid() || auction.settled_count == auction.bidder_count,
+        AuctionError::UnsettledCommittedBids
+    );
+    // Multi-unit winners all pay the marginal unit price, so that is the
+    // price the reserve is held against.
+    let reserve_reference = if auction.auction_type == AuctionType::MultiUnit {
+        let unit_book = ctx
+            .accounts
+            .unit_book
+            .as_ref()
+            .ok_or(AuctionError::MissingUnitBook)?;
+        auction.units_sold = unit_book.units_allocated(auction.unit_count);
+        auction.clearing_price = unit_book.marginal_price();
+        auction.clearing_price
+    } else {
+        auction.highest_bid
+    };
+
+    auction.outcome = if auction.highest_bid == 0 {
+        AuctionOutcome::NoBids
+    } else if auction.has_reserve()
+        && (!auction.reserve_revealed || reserve_reference < auction.reserve_price)
+    {
+        AuctionOutcome::ReserveNotMet
+    } else {
+        AuctionOutcome::Sold
+    };
+    if auction.outcome == AuctionOutcome::Sold {
+        // A second-price runner-up can sit below the reserve; the seller
+        // still never receives less than the price they committed to.
+        auction.clearing_price = if auction.has_reserve() {
+            auction.winning_price().max(auction.reserve_price)
+        } else {
+            auction.winning_price()
+        };
+    } else {
+        auction.winner = Pubkey::default();
+        auction.clearing_price = 0;
+        auction.units_sold = 0;
+    }
+    auction.finalized = true;
+
+    emit!(AuctionFinalized {
+        auction: auction.key(),
+        outcome: auction.outcome,
+        winner: auction.winner,
+        final_bid: auction.highest_bid,
+        clearing_price: auction.clearing_price,
+    });
+
+    Ok(())
+}
+
+/// Cancels an auction before `end_time`. English auctions can no longer be
+/// cancelled once they have a leader.
+///
+/// The auction is closed out as finalized with a `Cancelled` outcome and no
+/// winner, so every bidder refunds their full deposit through `claim_refund`
+/// and any escrowed item returns to the authority through `claim_item`.
+pub fn cancel_auction_handler(ctx: Context<CancelAuction>) -> Result<()> {
+    let auction = &mut ctx.accounts.auction_house;
+    require!(!auction.finalized, AuctionError::AuctionFinalized);
+    require!(
+        Clock::get()?.unix_timestamp < auction.end_time,
+        AuctionError::AuctionEnded
+    );
+    require!(
+        auction.winner == Pubkey::default(),
+        AuctionError::CancelNotAllowed
+    );
+
+    auction.outcome = AuctionOutcome::Cancelled;
+    auction.winner = Pubkey::default();
+    auction.highest_bid = 0;
+    auction.second_highest_bid = 0;
+    auction.clearing_price = 0;
+    auction.finalized = true;
+
+    emit!(AuctionCancelled {
+        auction: auction.key(),
+        authority: auction.authority,
+        bidder_count: auction.bidder_count,
+    });
+
+    Ok(())
+}
+
+/// Allows seller to withdraw winning proceeds and forfeited deposits from the escrow vault.
+///
+/// After the platform fee, each proceeds split receives its share rounded
+/// down, in registration order, and the authority receives the remainder,
+/// so all rounding dust lands with the authority.
+pub fn claim_seller_proceeds_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimSellerProceeds<'info>>,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction_house;
+    require!(auction.finalized, AuctionError::AuctionNotFinalized);
     require!(
         !auction.proceeds_claimed,
         AuctionError::ProceedsAlreadyClaimed
     );
-    require!(auction.highest_bid > 0, AuctionError::NoWinningBid);
+    require!(
+        auction.outcome != AuctionOutcome::Cancelled,
+        AuctionError::AuctionCancelled
+    );
+    require!(
+        auction.outcome != AuctionOutcome::ReserveNotMet,
+        AuctionError::ReserveNotMet
+    );
+
+    let gross = auction
+        .sale_proceeds()
+        .and_then(|proceeds| proceeds.checked_add(auction.forfeited_amount))
+        .ok_or(AuctionError::MathOverflow)?;
+    require!(gross > 0, AuctionError::NoWinningBid);
+    let fee = auction.fee_for(gross);
+    let amount = gross.checked_sub(fee).ok_or(AuctionError::MathOverflow)?;
+
+    let mut fee_recipient = Pubkey::default();
+    if fee > 0 {
+        let Some(config) = &ctx.accounts.config else {
+            return err!(AuctionError::MissingFeeAccounts);
+        };
+        fee_recipient = config.fee_recipient;
+        match token_payment(
+            auction,
+            &ctx.accounts.payment_mint,
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.fee_recipient_token_account,
+            &ctx.accounts.token_program,
+        )? {
+            Some(payment) => {
+                require_keys_eq!(
+                    payment.user_token_account.owner,
+                    fee_recipient,
+                    AuctionError::FeeRecipientMismatch
+                );
+                transfer_tokens_from_vault(
+                    &auction.key(),
+                    auction,
+                    &ctx.accounts.vault.to_account_info(),
+                    &payment,
+                    fee,
+                )?;
+            }
+            None => {
+                let Some(fee_recipient_info) = &ctx.accounts.fee_recipient else {
+                    return err!(AuctionError::MissingFeeAccounts);
+                };
+                require_keys_eq!(
+                    fee_recipient_info.key(),
+                    fee_recipient,
+                    AuctionError::FeeRecipientMismatch
+                );
+                transfer_from_vault(
+                    &ctx.accounts.vault.to_account_info(),
+                    &fee_recipient_info.to_account_info(),
+                    fee,
+                )?;
+            }
+        }
+    }
+
+    let auction_key = auction.key();
+    let splits = auction.proceeds_splits.clone();
+    require!(
+        ctx.remaining_accounts.len() == splits.len(),
+        AuctionError::SplitRecipientMismatch
+    );
+    // Splits and the authority share one payment context; each split swaps in
+    // its own token account.
+    let payment = token_payment(
+        auction,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.authority_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    let rent = Rent::get()?;
+    let mut remainder = amount;
+    for (split, recipient_info) in splits.iter().zip(ctx.remaining_accounts) {
+        // Token shares go to the recipient's associated token account, whose
+        // address can be checked even after the account has been closed.
+        let expected = match &payment {
+            Some(payment) => get_associated_token_address_with_program_id(
+                &split.recipient,
+                &auction.payment_mint,
+                &payment.token_program.key(),
+            ),
+            None => split.recipient,
+        };
+        require_keys_eq!(
+            recipient_info.key(),
+            expected,
+            AuctionError::SplitRecipientMismatch
+        );
+
+        let share = split.share_of(amount);
+        if share == 0 {
+            continue;
+        }
 
-    transfer_from_vault(
+        // A share the recipient can't take (a closed, frozen or reassigned
+        // token account, or lamports that would leave it below rent exemption)
+        // stays in the remainder instead of blocking the claim.
+        match &payment {
+            Some(payment) => {
+                let Some(split_account) =
+                    InterfaceAccount::<TokenAccount>::try_from(recipient_info)
+                        .ok()
+                        .filter(|account| account.owner == split.recipient && !account.is_frozen())
+                else {
+                    continue;
+                };
+                transfer_tokens_from_vault(
+                    &auction_key,
+                    auction,
+                    &ctx.accounts.vault.to_account_info(),
+                    &VaultTokenAccounts {
+                        user_token_account: &split_account,
+                        ..*payment
+                    },
+                    share,
+                )?;
+            }
+            None => {
+                let funded = recipient_info
+                    .lamports()
+                    .checked_add(share)
+                    .ok_or(AuctionError::MathOverflow)?;
+                if !rent.is_exempt(funded, recipient_info.data_len()) {
+                    continue;
+                }
+                transfer_from_vault(&ctx.accounts.vault.to_account_info(), recipient_info, share)?;
+            }
+        }
+        remainder = remainder
+            .checked_sub(share)
+            .ok_or(AuctionError::MathOverflow)?;
+
+        emit!(ProceedsDistributed {
+            auction: auction_key,
+            recipient: split.recipient,
+            amount: share,
+        });
+    }
+
+    payout_from_vault(
+        &auction_key,
+        auction,
         &ctx.accounts.vault.to_account_info(),
         &ctx.accounts.authority.to_account_info(),
-        auction.highest_bid,
+        payment,
+        remainder,
     )?;
 
     auction.proceeds_claimed = true;
 
     emit!(SellerProceedsClaimed {
-        auction: auction.key(),
+        auction: auction_key,
         authority: auction.authority,
-        amount: auction.highest_bid,
+        amount: remainder,
+        fee,
+        fee_recipient,
     });
 
     Ok(())
 }
 
-/// Allows losing bidders to reclaim their escrow deposits.
-pub fn claim_refund_handler(ctx: Context<ClaimRefund>) -> Result<()> {
+/// Sends the escrowed item to the winner, or returns it to the authority when
+/// there is no winning bid. Only the entitled party can claim it. In
+/// multi-unit auctions winners use `claim_units` instead and this returns
+/// the unsold lots, plus any rounding remainder, to the authority.
+pub fn claim_item_handler(ctx: Context<ClaimItem>) -> Result<()> {
+    let auction_key = ctx.accounts.auction_house.key();
+    let auction = &mut ctx.accounts.auction_house;
+    require!(auction.finalized, AuctionError::AuctionNotFinalized);
+    require!(auction.has_escrowed_item(), AuctionError::NoEscrowedItem);
+    require!(!auction.item_claimed, AuctionError::ItemAlreadyClaimed);
+
+    let (recipient, amount) = if auction.auction_type == AuctionType::MultiUnit {
+        let sold = auction
+            .unit_size()
+            .checked_mul(u64::from(auction.units_sold))
+            .ok_or(AuctionError::MathOverflow)?;
+        let unsold = auction
+            .item_amount
+            .checked_sub(sold)
+            .ok_or(AuctionError::MathOverflow)?;
+        (auction.authority, unsold)
+    } else if auction.winner != Pubkey::default() {
+        (auction.winner, auction.item_amount)
+    } else {
+        (auction.authority, auction.item_amount)
+    };
+    require_keys_eq!(
+        ctx.accounts.claimer.key(),
+        recipient,
+        AuctionError::ItemClaimNotAllowed
+    );
+
+    if amount > 0 {
+        transfer_tokens_from_vault(
+            &auction_key,
+            auction,
+            &ctx.accounts.vault.to_account_info(),
+            &VaultTokenAccounts {
+                mint: &ctx.accounts.item_mint,
+                vault_token_account: &ctx.accounts.item_vault,
+                user_token_account: &ctx.accounts.recipient_item_account,
+                token_program: &ctx.accounts.item_token_program,
+            },
+            amount,
+        )?;
+    }
+
+    auction.item_claimed = true;
+
+    emit!(ItemClaimed {
+        auction: auction_key,
+        recipient,
+        item_mint: auction.item_mint,
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Settles a bidder's position in a finalized multi-unit auction: the lots
+/// won are delivered and whatever the deposit holds beyond
+/// `clearing_price * units` is refunded. Losing bidders get their full deposit.
+pub fn claim_units_handler(ctx: Context<ClaimUnits>) -> Result<()> {
+    let auction_key = ctx.accounts.auction_house.key();
     let auction = &ctx.accounts.auction_house;
+    require!(
+        auction.auction_type == AuctionType::MultiUnit,
+        AuctionError::UnsupportedAuctionType
+    );
     require!(auction.finalized, AuctionError::AuctionNotFinalized);
 
     let sealed_bid = &mut ctx.accounts.sealed_bid;
     require!(
-        sealed_bid.bidder != auction.winner,
-        AuctionError::WinnerNoRefund
+        !sealed_bid.refund_claimed,
+        AuctionError::RefundAlreadyClaimed
+    );
+    require!(!sealed_bid.is_forfeited(), AuctionError::BidForfeited);
+
+    let units = if auction.outcome == AuctionOutcome::Sold && sealed_bid.revealed {
+        ctx.accounts
+            .unit_book
+            .allocation(&sealed_bid.bidder, auction.unit_count)
+    } else {
+        0
+    };
+    let cost = auction
+        .clearing_price
+        .checked_mul(u64::from(units))
+        .ok_or(AuctionError::MathOverflow)?;
+    let refund = sealed_bid
+        .deposited
+        .checked_sub(cost)
+        .ok_or(AuctionError::MathOverflow)?;
+    let item_amount = auction
+        .unit_size()
+        .checked_mul(u64::from(units))
+        .ok_or(AuctionError::MathOverflow)?;
+
+    sealed_bid.refund_claimed = true;
+
+    if item_amount > 0 {
+        let Some(recipient_item_account) = &ctx.accounts.recipient_item_account else {
+            return err!(AuctionError::MissingItemAccounts);
+        };
+        transfer_tokens_from_vault(
+            &auction_key,
+            auction,
+            &ctx.accounts.vault.to_account_info(),
+            &VaultTokenAccounts {
+                mint: &ctx.accounts.item_mint,
+                vault_token_account: &ctx.accounts.item_vault,
+                user_token_account: recipient_item_account,
+                token_program: &ctx.accounts.item_token_program,
+            },
+            item_amount,
+        )?;
+    }
+
+    if refund > 0 {
+        let payment = token_payment(
+            auction,
+            &ctx.accounts.payment_mint,
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.bidder_token_account,
+            &ctx.accounts.token_program,
+        )?;
+        payout_from_vault(
+            &auction_key,
+            auction,
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.bidder.to_account_info(),
+            payment,
+            refund,
+        )?;
+    }
+
+    emit!(UnitsClaimed {
+        auction: auction_key,
+        bidder: sealed_bid.bidder,
+        units,
+        item_amount,
+        refund,
+    });
+
+    Ok(())
+}
+
+/// Allows losing bidders to reclaim their escrow deposits and the winner to
+/// reclaim any deposit above the clearing price. In English auctions an
+/// outbid bidder may withdraw while bidding is still open.
+pub fn claim_refund_handler(ctx: Context<ClaimRefund>) -> Result<()> {
+    let auction = &ctx.accounts.auction_house;
+    require!(
+        auction.auction_type != AuctionType::MultiUnit,
+        AuctionError::UnsupportedAuctionType
     );
+    let sealed_bid = &mut ctx.accounts.sealed_bid;
+    if !auction.finalized {
+        require!(
+            auction.auction_type == AuctionType::English,
+            AuctionError::AuctionNotFinalized
+        );
+        require!(
+            sealed_bid.bidder != auction.winner,
+            AuctionError::LeaderCannotWithdraw
+        );
+    }
+
     require!(
         !sealed_bid.refund_claimed,
         AuctionError::RefundAlreadyClaimed
     );
-    require!(sealed_bid.deposited > 0, AuctionError::NoRefundAvailable);
+    require!(!sealed_bid.is_forfeited(), AuctionError::BidForfeited);
+
+    let refund_amount = sealed_bid.refundable_amount(auction);
+    require!(
+        refund_amount > 0 || sealed_bid.bidder != auction.winner,
+        AuctionError::WinnerNoRefund
+    );
+    require!(refund_amount > 0, AuctionError::NoRefundAvailable);
 
-    let refund_amount = sealed_bid.deposited;
     sealed_bid.refund_claimed = true;
 
-    transfer_from_vault(
+    let payment = token_payment(
+        auction,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.bidder_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    payout_from_vault(
+        &auction.key(),
+        auction,
         &ctx.accounts.vault.to_account_info(),
         &ctx.accounts.bidder.to_account_info(),
+        payment,
         refund_amount,
     )?;
 
@@ -331,6 +2061,90 @@ pub fn claim_refund_handler(ctx: Context<ClaimRefund>) -> Result<()> {
 }
 
 /// Closes a settled bid account; constraints enforce close safety.
-pub fn close_sealed_bid_handler(_ctx: Context<CloseSealedBid>) -> Result<()> {
+pub fn close_sealed_bid_handler(ctx: Context<CloseSealedBid>) -> Result<()> {
+    let auction = &mut ctx.accounts.auction_house;
+    auction.open_bid_count = auction
+        .open_bid_count
+        .checked_sub(1)
+        .ok_or(AuctionError::MathOverflow)?;
+
+    Ok(())
+}
+
+/// Closes a finished auction once nothing is owed to anyone: proceeds are
+/// claimed (or there was no sale), the item is delivered and every bid
+/// account is closed. Leftover vault balances and rent go to the authority.
+///
+/// Token-2022 vaults with withheld transfer fees must be harvested first,
+/// otherwise closing the token account fails.
+pub fn close_auction_handler(ctx: Context<CloseAuction>) -> Result<()> {
+    let auction_key = ctx.accounts.auction_house.key();
+    let auction = &ctx.accounts.auction_house;
+    require!(auction.finalized, AuctionError::AuctionNotFinalized);
+    require!(
+        !matches!(
+            auction.outcome,
+            AuctionOutcome::Sold | AuctionOutcome::BoughtNow
+        ) || auction.proceeds_claimed,
+        AuctionError::ProceedsNotClaimed
+    );
+    require!(
+        !auction.has_escrowed_item() || auction.item_claimed,
+        AuctionError::ItemNotClaimed
+    );
+    require!(auction.open_bid_count == 0, AuctionError::OutstandingBids);
+
+    let vault = ctx.accounts.vault.to_account_info();
+    let authority = ctx.accounts.authority.to_account_info();
+
+    let mut swept_tokens = 0;
+    if let Some(payment) = token_payment(
+        auction,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.authority_token_account,
+        &ctx.accounts.token_program,
+    )? {
+        swept_tokens = payment.vault_token_account.amount;
+        if swept_tokens > 0 {
+            transfer_tokens_from_vault(&auction_key, auction, &vault, &payment, swept_tokens)?;
+        }
+        close_vault_token_account(
+            &auction_key,
+            auction,
+            &vault,
+            payment.vault_token_account,
+            &authority,
+            payment.token_program,
+        )?;
+    }
+
+    if auction.has_escrowed_item() {
+        let (Some(item_vault), Some(item_token_program)) =
+            (&ctx.accounts.item_vault, &ctx.accounts.item_token_program)
+        else {
+            return err!(AuctionError::MissingItemAccounts);
+        };
+        close_vault_token_account(
+            &auction_key,
+            auction,
+            &vault,
+            item_vault,
+            &authority,
+            item_token_program,
+        )?;
+    }
+
+    // Draining every lamport lets the runtime reclaim the vault PDA.
+    let swept_lamports = vault.lamports();
+    transfer_from_vault(&vault, &authority, swept_lamports)?;
+
+    emit!(AuctionClosed {
+        auction: auction_key,
+        authority: auction.authority,
+        swept_lamports,
+        swept_tokens,
+    });
+
     Ok(())
 }
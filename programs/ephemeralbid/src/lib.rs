@@ -11,6 +11,7 @@ pub mod utils;
 
 use contexts::*;
 use handlers::*;
+use state::CreateAuctionParams;
 
 declare_id!("HK92WjG3LE4JCWi5mU7pAvLz1JFBUwwh1v3obB9ajwnE");
 
@@ -19,14 +20,37 @@ declare_id!("HK92WjG3LE4JCWi5mU7pAvLz1JFBUwwh1v3obB9ajwnE");
 pub mod ephemeralbid {
     use super::*;
 
-    pub fn create_auction(
-        ctx: Context<CreateAuction>,
-        auction_id: u64,
-        min_bid: u64,
-        min_increment: u64,
-        duration: i64,
+    pub fn initialize_config(ctx: Context<InitializeConfig>, fee_bps: u16) -> Result<()> {
+        initialize_config_handler(ctx, fee_bps)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, admin: Pubkey, fee_bps: u16) -> Result<()> {
+        update_config_handler(ctx, admin, fee_bps)
+    }
+
+    pub fn create_auction(ctx: Context<CreateAuction>, params: CreateAuctionParams) -> Result<()> {
+        create_auction_handler(ctx, params)
+    }
+
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        session_signer: Pubkey,
+        expires_at: i64,
+        max_spend: u64,
     ) -> Result<()> {
-        create_auction_handler(ctx, auction_id, min_bid, min_increment, duration)
+        create_session_handler(ctx, session_signer, expires_at, max_spend)
+    }
+
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+        revoke_session_handler(ctx)
+    }
+
+    pub fn delegate_session(ctx: Context<DelegateSession>) -> Result<()> {
+        delegate_session_handler(ctx)
+    }
+
+    pub fn undelegate_session(ctx: Context<UndelegateSession>) -> Result<()> {
+        undelegate_session_handler(ctx)
     }
 
     pub fn initialize_sealed_bid(ctx: Context<InitializeSealedBid>) -> Result<()> {
@@ -37,30 +61,123 @@ pub mod ephemeralbid {
         delegate_bid_handler(ctx)
     }
 
-    pub fn submit_sealed_bid(ctx: Context<SubmitSealedBid>, amount: u64) -> Result<()> {
-        submit_sealed_bid_handler(ctx, amount)
+    pub fn create_bid_permission(ctx: Context<CreateBidPermission>) -> Result<()> {
+        create_bid_permission_handler(ctx)
+    }
+
+    pub fn delegate_bid_permission(ctx: Context<DelegateBidPermission>) -> Result<()> {
+        delegate_bid_permission_handler(ctx)
+    }
+
+    pub fn delegate_auction(ctx: Context<DelegateAuction>) -> Result<()> {
+        delegate_auction_handler(ctx)
+    }
+
+    pub fn submit_sealed_bid(ctx: Context<SubmitSealedBid>, commitment: [u8; 32]) -> Result<()> {
+        submit_sealed_bid_handler(ctx, commitment)
+    }
+
+    pub fn place_open_bid(ctx: Context<PlaceOpenBid>, amount: u64) -> Result<()> {
+        place_open_bid_handler(ctx, amount)
+    }
+
+    pub fn deposit_to_escrow(ctx: Context<DepositToEscrow>, amount: u64) -> Result<()> {
+        deposit_to_escrow_handler(ctx, amount)
+    }
+
+    pub fn place_escrowed_bid(ctx: Context<PlaceEscrowedBid>, amount: u64) -> Result<()> {
+        place_escrowed_bid_handler(ctx, amount)
+    }
+
+    pub fn accept_dutch_price(ctx: Context<AcceptDutchPrice>) -> Result<()> {
+        accept_dutch_price_handler(ctx)
+    }
+
+    pub fn buy_now(ctx: Context<BuyNow>) -> Result<()> {
+        buy_now_handler(ctx)
     }
 
     pub fn commit_bid(ctx: Context<CommitBid>) -> Result<()> {
         commit_bid_handler(ctx)
     }
 
+    pub fn commit_and_undelegate_bid(ctx: Context<CommitAndUndelegateBid>) -> Result<()> {
+        commit_and_undelegate_bid_handler(ctx)
+    }
+
+    pub fn release_bid_permission(ctx: Context<ReleaseBidPermission>) -> Result<()> {
+        release_bid_permission_handler(ctx)
+    }
+
+    pub fn commit_auction(ctx: Context<CommitAuction>) -> Result<()> {
+        commit_auction_handler(ctx)
+    }
+
+    pub fn undelegate_auction(ctx: Context<UndelegateAuction>) -> Result<()> {
+        undelegate_auction_handler(ctx)
+    }
+
     pub fn commit_bid_l1(ctx: Context<CommitBidL1>) -> Result<()> {
         commit_bid_l1_handler(ctx)
     }
 
+    pub fn withdraw_bid(ctx: Context<WithdrawBid>) -> Result<()> {
+        withdraw_bid_handler(ctx)
+    }
+
+    pub fn reveal_bid(ctx: Context<RevealBid>, amount: u64, salt: [u8; 32]) -> Result<()> {
+        reveal_bid_handler(ctx, amount, salt)
+    }
+
+    pub fn reveal_unit_bid(
+        ctx: Context<RevealBid>,
+        unit_price: u64,
+        quantity: u32,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        reveal_unit_bid_handler(ctx, unit_price, quantity, salt)
+    }
+
     pub fn settle_committed_bid(ctx: Context<SettleCommittedBid>) -> Result<()> {
         settle_committed_bid_handler(ctx)
     }
 
+    pub fn settle_committed_bids<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleCommittedBids<'info>>,
+    ) -> Result<()> {
+        settle_committed_bids_handler(ctx)
+    }
+
+    pub fn reveal_reserve(
+        ctx: Context<RevealReserve>,
+        reserve_price: u64,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        reveal_reserve_handler(ctx, reserve_price, salt)
+    }
+
     pub fn finalize_auction(ctx: Context<FinalizeAuction>) -> Result<()> {
         finalize_auction_handler(ctx)
     }
 
-    pub fn claim_seller_proceeds(ctx: Context<ClaimSellerProceeds>) -> Result<()> {
+    pub fn cancel_auction(ctx: Context<CancelAuction>) -> Result<()> {
+        cancel_auction_handler(ctx)
+    }
+
+    pub fn claim_seller_proceeds<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimSellerProceeds<'info>>,
+    ) -> Result<()> {
         claim_seller_proceeds_handler(ctx)
     }
 
+    pub fn claim_item(ctx: Context<ClaimItem>) -> Result<()> {
+        claim_item_handler(ctx)
+    }
+
+    pub fn claim_units(ctx: Context<ClaimUnits>) -> Result<()> {
+        claim_units_handler(ctx)
+    }
+
     pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
         claim_refund_handler(ctx)
     }
@@ -68,4 +185,8 @@ pub mod ephemeralbid {
     pub fn close_sealed_bid(ctx: Context<CloseSealedBid>) -> Result<()> {
         close_sealed_bid_handler(ctx)
     }
+
+    pub fn close_auction(ctx: Context<CloseAuction>) -> Result<()> {
+        close_auction_handler(ctx)
+    }
 }
@@ -1,26 +1,420 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{BPS_DENOMINATOR, MAX_AUCTION_UNITS, MAX_PROCEEDS_SPLITS};
+
+/// Protocol-wide settings shared by every auction.
+#[account]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,
+    /// Platform fee taken from seller proceeds, in basis points.
+    pub fee_bps: u16,
+    pub fee_recipient: Pubkey,
+    pub bump: u8,
+}
+
+impl ProtocolConfig {
+    pub const LEN: usize = 32 + 2 + 32 + 1;
+}
+
 /// Global auction state for one auction instance.
 #[account]
+#[derive(Default)]
 pub struct AuctionHouse {
     pub authority: Pubkey,
     pub auction_id: u64,
     pub min_bid: u64,
     pub min_increment: u64,
+    pub auction_type: AuctionType,
+    pub tie_break: TieBreak,
+    /// Dutch auctions: opening ask, decaying toward `min_bid` as the floor.
+    pub start_price: u64,
+    /// Dutch auctions: amount the ask drops every `decay_interval` seconds.
+    pub price_decrement: u64,
+    pub decay_interval: i64,
+    /// Price at which a bidder can end the auction at once; zero disables it.
+    pub buy_now_price: u64,
+    /// Whether bidders may pull a sealed bid before `end_time`.
+    pub withdrawal_policy: WithdrawalPolicy,
+    /// Share of the deposit kept for the seller under `AllowedWithPenalty`.
+    pub withdrawal_penalty_bps: u16,
+    /// SPL mint bids are denominated in; `Pubkey::default()` means lamports.
+    pub payment_mint: Pubkey,
+    /// Mint of the escrowed lot; `Pubkey::default()` when nothing is escrowed.
+    pub item_mint: Pubkey,
+    pub item_amount: u64,
+    /// Multi-unit auctions: number of equal lots `item_amount` is split into.
+    pub unit_count: u32,
+    /// Multi-unit auctions: lots allocated to winners at finalization.
+    pub units_sold: u32,
+    /// Hash of the sealed reserve price; all zeroes when there is no reserve.
+    pub reserve_commitment: [u8; 32],
+    pub reserve_price: u64,
+    pub reserve_revealed: bool,
     pub highest_bid: u64,
+    /// Runner-up revealed amount, tracked for second-price settlement.
+    pub second_highest_bid: u64,
+    /// Price the winner pays, fixed at finalization. Per unit in multi-unit
+    /// auctions.
+    pub clearing_price: u64,
     pub winner: Pubkey,
+    /// `committed_at` of the current winner's bid, used for tie-breaking.
+    pub winner_committed_at: i64,
+    pub start_time: i64,
     pub end_time: i64,
+    /// Seconds after `end_time` during which committed bids may be revealed.
+    pub reveal_duration: i64,
+    /// Bids landing within this many seconds of `end_time` extend the auction.
+    pub extension_window: i64,
+    /// Seconds added to `end_time` by each late bid.
+    pub extension_duration: i64,
+    /// Soft-close extensions never push `end_time` past this cap.
+    pub hard_end_time: i64,
+    /// ER/PER validator bids of this auction must be delegated to.
+    pub er_validator: Pubkey,
+    /// How often the validator commits delegated bids back; zero uses the SDK default.
+    pub commit_frequency_ms: u32,
     pub bidder_count: u32,
+    /// Sealed-bid accounts not yet closed, each possibly holding a refund.
+    /// Must reach zero before the auction can be closed.
+    pub open_bid_count: u32,
+    /// Committed bids seen by settlement; commits themselves happen on the
+    /// rollup and cannot write here.
     pub committed_count: u32,
+    /// Bid accounts processed by settlement, committed or not.
     pub settled_count: u32,
+    /// Deposits of bids left unrevealed past the reveal deadline, plus
+    /// withdrawal penalties; paid to the seller.
+    pub forfeited_amount: u64,
+    pub outcome: AuctionOutcome,
     pub finalized: bool,
     pub proceeds_claimed: bool,
+    pub item_claimed: bool,
+    /// Platform fee copied from the config at creation, so a later config
+    /// change cannot reprice a listed sale. Zero if no config existed yet.
+    pub fee_bps: u16,
+    /// Creators or co-owners paid a share of the seller proceeds; the
+    /// authority keeps the rest.
+    pub proceeds_splits: Vec<ProceedsSplit>,
     pub bump: u8,
     pub vault_bump: u8,
 }
 
 impl AuctionHouse {
-    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 32 + 8 + 4 + 4 + 4 + 1 + 1 + 1 + 1;
+    pub const LEN: usize = 32
+        + 8
+        + 8
+        + 8
+        + 1
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + 1
+        + 2
+        + 32
+        + 32
+        + 8
+        + 4
+        + 4
+        + 32
+        + 8
+        + 1
+        + 8
+        + 8
+        + 8
+        + 32
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 32
+        + 4
+        + 4
+        + 4
+        + 4
+        + 4
+        + 8
+        + 1
+        + 1
+        + 1
+        + 1
+        + 2
+        + (4 + MAX_PROCEEDS_SPLITS * ProceedsSplit::LEN)
+        + 1
+        + 1;
+
+    /// Platform fee owed on `amount`, rounded down.
+    pub fn fee_for(&self, amount: u64) -> u64 {
+        // fee_bps is capped at BPS_DENOMINATOR, so the fee never exceeds amount.
+        (u128::from(amount) * u128::from(self.fee_bps) / u128::from(BPS_DENOMINATOR)) as u64
+    }
+
+    /// Whether an item was escrowed at creation for delivery to the winner.
+    pub fn has_escrowed_item(&self) -> bool {
+        self.item_mint != Pubkey::default()
+    }
+
+    /// Whether the authority committed to a sealed reserve price at creation.
+    pub fn has_reserve(&self) -> bool {
+        self.reserve_commitment != [0; 32]
+    }
+
+    /// Whether bids are escrowed in an SPL token rather than lamports.
+    pub fn is_token_denominated(&self) -> bool {
+        self.payment_mint != Pubkey::default()
+    }
+
+    /// Last moment at which committed bids can still be revealed.
+    pub fn reveal_end_time(&self) -> i64 {
+        self.end_time.saturating_add(self.reveal_duration)
+    }
+
+    /// Folds a revealed bid into the highest/runner-up standings. Equal
+    /// amounts are resolved by `tie_break`, so the result does not depend on
+    /// the order bids are settled in.
+    pub fn record_revealed_bid(&mut self, bidder: Pubkey, amount: u64, committed_at: i64) {
+        let takes_lead = amount > self.highest_bid
+            || (amount == self.highest_bid
+                && self.winner != Pubkey::default()
+                && self.wins_tie(bidder, committed_at));
+        if takes_lead {
+            self.second_highest_bid = self.highest_bid;
+            self.highest_bid = amount;
+            self.winner = bidder;
+            self.winner_committed_at = committed_at;
+        } else if amount > self.second_highest_bid {
+            self.second_highest_bid = amount;
+        }
+    }
+
+    /// Whether a bid equal to the current highest should replace the winner.
+    fn wins_tie(&self, bidder: Pubkey, committed_at: i64) -> bool {
+        self.tie_break.prefers(
+            (bidder, committed_at),
+            (self.winner, self.winner_committed_at),
+        )
+    }
+
+    /// New `end_time` if a bid placed at `now` falls inside the soft-close
+    /// window, capped at `hard_end_time`; `None` when no extension applies.
+    pub fn soft_close_extension(&self, now: i64) -> Option<i64> {
+        if self.extension_window == 0 || self.end_time >= self.hard_end_time {
+            return None;
+        }
+        if now < self.end_time.saturating_sub(self.extension_window) {
+            return None;
+        }
+        Some(
+            self.end_time
+                .saturating_add(self.extension_duration)
+                .min(self.hard_end_time),
+        )
+    }
+
+    /// Price owed by the current winner under this auction's pricing rule.
+    /// A lone second-price bidder pays the auction minimum.
+    pub fn winning_price(&self) -> u64 {
+        if self.highest_bid == 0 {
+            return 0;
+        }
+        match self.auction_type {
+            AuctionType::FirstPrice => self.highest_bid,
+            AuctionType::SecondPrice => self.second_highest_bid.max(self.min_bid),
+            AuctionType::Dutch | AuctionType::English => self.highest_bid,
+            AuctionType::MultiUnit => self.clearing_price,
+        }
+    }
+
+    /// Whether bids go through the sealed commit/reveal/settle flow.
+    pub fn is_sealed_bid(&self) -> bool {
+        matches!(
+            self.auction_type,
+            AuctionType::FirstPrice | AuctionType::SecondPrice | AuctionType::MultiUnit
+        )
+    }
+
+    /// Amount of `item_mint` making up one lot of a multi-unit auction.
+    pub fn unit_size(&self) -> u64 {
+        self.item_amount / u64::from(self.unit_count.max(1))
+    }
+
+    /// Total owed to the seller for the sold lot(s), excluding forfeits.
+    pub fn sale_proceeds(&self) -> Option<u64> {
+        match self.auction_type {
+            AuctionType::MultiUnit => self.clearing_price.checked_mul(u64::from(self.units_sold)),
+            _ => Some(self.clearing_price),
+        }
+    }
+
+    /// Smallest amount the next English bid must reach: `min_bid` for the
+    /// opening bid, then the leader plus `min_increment` (at least one unit).
+    pub fn next_open_bid_minimum(&self) -> u64 {
+        if self.winner == Pubkey::default() {
+            return self.min_bid;
+        }
+        self.highest_bid.saturating_add(self.min_increment.max(1))
+    }
+
+    /// Current Dutch ask: `start_price` minus one `price_decrement` per
+    /// elapsed `decay_interval`, never below the `min_bid` floor.
+    pub fn dutch_price(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.start_time).max(0);
+        let steps = (elapsed / self.decay_interval.max(1)) as u64;
+        self.start_price
+            .saturating_sub(steps.saturating_mul(self.price_decrement))
+            .max(self.min_bid)
+    }
+}
+
+/// Instruction arguments for `create_auction`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateAuctionParams {
+    pub auction_id: u64,
+    pub min_bid: u64,
+    pub min_increment: u64,
+    /// Unix timestamp bidding opens at; zero starts the auction immediately.
+    pub start_time: i64,
+    /// Bidding length in seconds, counted from the start time.
+    pub duration: i64,
+    pub reveal_duration: i64,
+    pub auction_type: AuctionType,
+    pub tie_break: TieBreak,
+    /// Lots the escrowed item is split into; multi-unit auctions only.
+    pub unit_count: u32,
+    /// Dutch schedule; ignored for other auction types.
+    pub start_price: u64,
+    pub price_decrement: u64,
+    pub decay_interval: i64,
+    /// Buy-it-now price; zero for none.
+    pub buy_now_price: u64,
+    pub withdrawal_policy: WithdrawalPolicy,
+    /// Basis points of the deposit forfeited on withdrawal; penalty policy only.
+    pub withdrawal_penalty_bps: u16,
+    /// Amount of `item_mint` to escrow; ignored when no item accounts are passed.
+    pub item_amount: u64,
+    /// Hash of (reserve price, salt, auction); all zeroes for no reserve.
+    pub reserve_commitment: [u8; 32],
+    /// Soft-close window before `end_time`; zero disables extensions.
+    pub extension_window: i64,
+    pub extension_duration: i64,
+    /// Most seconds extensions may add on top of `duration` in total.
+    pub max_extension: i64,
+    /// ER/PER validator to delegate bids to; `Pubkey::default()` picks the
+    /// devnet default.
+    pub er_validator: Pubkey,
+    /// Commit frequency for delegated bids; zero uses the SDK default.
+    pub commit_frequency_ms: u32,
+    /// Up to `MAX_PROCEEDS_SPLITS` shares of the seller proceeds.
+    pub proceeds_splits: Vec<ProceedsSplit>,
+}
+
+/// A recipient's share of the seller proceeds, in basis points.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ProceedsSplit {
+    pub recipient: Pubkey,
+    pub bps: u16,
+}
+
+impl ProceedsSplit {
+    pub const LEN: usize = 32 + 2;
+
+    /// This recipient's cut of `amount`, rounded down.
+    pub fn share_of(&self, amount: u64) -> u64 {
+        // Shares sum to at most BPS_DENOMINATOR, so the cut never exceeds amount.
+        (u128::from(amount) * u128::from(self.bps) / u128::from(BPS_DENOMINATOR)) as u64
+    }
+}
+
+/// Pricing rule applied when an auction is finalized.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AuctionType {
+    /// Winner pays their own bid.
+    FirstPrice,
+    /// Winner pays the runner-up bid (Vickrey).
+    SecondPrice,
+    /// Descending ask; the first bidder to accept wins at the current price.
+    Dutch,
+    /// Open ascending bids; each bid must beat the leader by `min_increment`.
+    English,
+    /// Sealed bids for a quantity of `unit_count` lots at a unit price; the
+    /// top bids fill the lots and all winners pay the lowest accepted price.
+    MultiUnit,
+}
+
+impl Default for AuctionType {
+    fn default() -> Self {
+        Self::FirstPrice
+    }
+}
+
+/// Rule deciding the winner between equal highest bids.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Bid committed first wins; same-second commits fall back to the lowest
+    /// bidder pubkey.
+    EarliestCommit,
+    /// Lowest bidder pubkey wins, ignoring commit times.
+    LowestBidderKey,
+}
+
+impl Default for TieBreak {
+    fn default() -> Self {
+        Self::EarliestCommit
+    }
+}
+
+impl TieBreak {
+    /// Whether bid `a` ranks ahead of an equal-priced bid `b`, each given as
+    /// `(bidder, committed_at)`.
+    pub fn prefers(&self, a: (Pubkey, i64), b: (Pubkey, i64)) -> bool {
+        match self {
+            TieBreak::EarliestCommit => (a.1, a.0) < (b.1, b.0),
+            TieBreak::LowestBidderKey => a.0 < b.0,
+        }
+    }
+}
+
+/// Whether a sealed bid may be withdrawn while bidding is open.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum WithdrawalPolicy {
+    /// Deposits stay locked until the auction is finalized.
+    Forbidden,
+    /// The full deposit is returned.
+    Allowed,
+    /// `withdrawal_penalty_bps` of the deposit stays in the vault for the seller.
+    AllowedWithPenalty,
+}
+
+impl Default for WithdrawalPolicy {
+    fn default() -> Self {
+        Self::Forbidden
+    }
+}
+
+/// Result recorded when an auction is finalized.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AuctionOutcome {
+    Pending,
+    Sold,
+    /// No revealed bid reached settlement.
+    NoBids,
+    /// Highest bid fell below the reserve, or the reserve was never revealed;
+    /// nobody wins.
+    ReserveNotMet,
+    /// Authority aborted the auction; every deposit is refundable.
+    Cancelled,
+    /// A bidder paid the buy-it-now price before the auction ended.
+    BoughtNow,
+}
+
+impl Default for AuctionOutcome {
+    fn default() -> Self {
+        Self::Pending
+    }
 }
 
 /// Lifecycle of an individual bidder's private bid account.
@@ -38,20 +432,237 @@ impl Default for BidStatus {
 }
 
 /// Bidder-specific sealed bid state. Delegated/committed via ER/PER flow.
+///
+/// While bidding is open only `commitment` (a hash of amount, salt and
+/// bidder) is stored; `amount` stays zero until `reveal_bid`.
 #[account]
 #[derive(Default)]
 pub struct SealedBid {
     pub auction: Pubkey,
     pub bidder: Pubkey,
+    pub commitment: [u8; 32],
     pub amount: u64,
+    /// Lots requested in a multi-unit auction; `amount` is then the unit price.
+    pub quantity: u32,
     pub deposited: u64,
     pub status: BidStatus,
     pub committed: bool,
+    /// Unix timestamp of the first commit, used for tie-breaking.
+    pub committed_at: i64,
+    pub revealed: bool,
     pub settled: bool,
     pub refund_claimed: bool,
+    /// Part of `deposited` already charged to the bidder's session key.
+    pub session_charged: u64,
     pub bump: u8,
 }
 
 impl SealedBid {
-    pub const LEN: usize = 32 + 32 + 8 + 8 + 1 + 1 + 1 + 1 + 1;
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 4 + 8 + 1 + 1 + 8 + 1 + 1 + 1 + 8 + 1;
+
+    /// A committed bid settled without being revealed loses its deposit.
+    pub fn is_forfeited(&self) -> bool {
+        self.settled && self.committed && !self.revealed
+    }
+
+    /// Escrow still owed back to this bidder after finalization: the full
+    /// deposit for non-winners, the excess over the clearing price for the winner.
+    pub fn refundable_amount(&self, auction: &AuctionHouse) -> u64 {
+        if self.refund_claimed || self.is_forfeited() {
+            return 0;
+        }
+        if self.bidder == auction.winner {
+            self.deposited.saturating_sub(auction.clearing_price)
+        } else {
+            self.deposited
+        }
+    }
+}
+
+/// Revealed bids of a multi-unit auction that still fall within the listed
+/// units, best first. A bid whose predecessors already fill every unit can
+/// never win again and is dropped, so the book holds at most `unit_count`
+/// entries and its contents do not depend on settlement order.
+#[account]
+#[derive(Default)]
+pub struct UnitBook {
+    pub auction: Pubkey,
+    pub entries: Vec<UnitBookEntry>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct UnitBookEntry {
+    pub bidder: Pubkey,
+    pub unit_price: u64,
+    pub quantity: u32,
+    pub committed_at: i64,
+}
+
+impl UnitBookEntry {
+    pub const LEN: usize = 32 + 8 + 4 + 8;
+}
+
+impl UnitBook {
+    pub const LEN: usize = 32 + 4 + MAX_AUCTION_UNITS as usize * UnitBookEntry::LEN + 1;
+
+    /// Inserts a revealed bid in rank order and drops entries pushed past the
+    /// listed units.
+    pub fn insert(&mut self, unit_count: u32, tie_break: TieBreak, entry: UnitBookEntry) {
+        let position = self
+            .entries
+            .iter()
+            .position(|other| Self::ranks_ahead(tie_break, &entry, other))
+            .unwrap_or(self.entries.len());
+        self.entries.insert(position, entry);
+
+        let mut filled: u32 = 0;
+        let mut kept = 0;
+        for existing in &self.entries {
+            if filled >= unit_count {
+                break;
+            }
+            filled = filled.saturating_add(existing.quantity);
+            kept += 1;
+        }
+        self.entries.truncate(kept);
+    }
+
+    /// Units won across the book, capped at `unit_count`.
+    pub fn units_allocated(&self, unit_count: u32) -> u32 {
+        self.entries
+            .iter()
+            .fold(0u32, |filled, entry| filled.saturating_add(entry.quantity))
+            .min(unit_count)
+    }
+
+    /// Unit price of the last accepted bid, which every winner pays.
+    pub fn marginal_price(&self) -> u64 {
+        self.entries.last().map_or(0, |entry| entry.unit_price)
+    }
+
+    /// Units won by `bidder`; the marginal bid may be filled partially.
+    pub fn allocation(&self, bidder: &Pubkey, unit_count: u32) -> u32 {
+        let mut filled: u32 = 0;
+        for entry in &self.entries {
+            let available = unit_count.saturating_sub(filled);
+            if entry.bidder == *bidder {
+                return entry.quantity.min(available);
+            }
+            filled = filled.saturating_add(entry.quantity);
+        }
+        0
+    }
+
+    fn ranks_ahead(tie_break: TieBreak, a: &UnitBookEntry, b: &UnitBookEntry) -> bool {
+        a.unit_price > b.unit_price
+            || (a.unit_price == b.unit_price
+                && tie_break.prefers((a.bidder, a.committed_at), (b.bidder, b.committed_at)))
+    }
+}
+
+/// Delegated signing key a bidder registers on L1 so a hot key can submit and
+/// commit their sealed bids without a wallet prompt each time. Submitting
+/// charges the session, so it has to be delegated alongside bids it signs for
+/// inside the rollup.
+#[account]
+pub struct SessionKey {
+    pub bidder: Pubkey,
+    pub session_signer: Pubkey,
+    pub expires_at: i64,
+    /// Most escrow the session may put behind bids, across all of them.
+    pub max_spend: u64,
+    /// Escrow the session has put behind bids so far.
+    pub spent: u64,
+    pub bump: u8,
+}
+
+impl SessionKey {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(seed: u8, unit_price: u64, quantity: u32) -> UnitBookEntry {
+        UnitBookEntry {
+            bidder: Pubkey::new_from_array([seed; 32]),
+            unit_price,
+            quantity,
+            committed_at: i64::from(seed),
+        }
+    }
+
+    fn book_from(unit_count: u32, entries: &[UnitBookEntry]) -> UnitBook {
+        let mut book = UnitBook::default();
+        for entry in entries {
+            book.insert(unit_count, TieBreak::EarliestCommit, *entry);
+        }
+        book
+    }
+
+    fn summary(book: &UnitBook) -> Vec<(Pubkey, u64, u32)> {
+        book.entries
+            .iter()
+            .map(|entry| (entry.bidder, entry.unit_price, entry.quantity))
+            .collect()
+    }
+
+    #[test]
+    fn fee_for_rounds_down_and_never_exceeds_amount() {
+        let auction = |fee_bps| AuctionHouse {
+            fee_bps,
+            ..AuctionHouse::default()
+        };
+
+        assert_eq!(auction(0).fee_for(1_000_000), 0);
+        assert_eq!(auction(250).fee_for(1_000_000), 25_000);
+        // 2.5% of 999 is 24.975.
+        assert_eq!(auction(250).fee_for(999), 24);
+        assert_eq!(auction(1).fee_for(9_999), 0);
+        assert_eq!(auction(BPS_DENOMINATOR).fee_for(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn unit_book_truncation_ignores_settlement_order() {
+        let bids = [
+            entry(1, 500, 2),
+            entry(2, 300, 3),
+            entry(3, 700, 1),
+            entry(4, 300, 1),
+            entry(5, 100, 4),
+        ];
+        let expected = summary(&book_from(4, &bids));
+        // 700x1 + 500x2 fill three units; the first 300 bid takes the last one.
+        assert_eq!(
+            expected,
+            vec![
+                (bids[2].bidder, 700, 1),
+                (bids[0].bidder, 500, 2),
+                (bids[1].bidder, 300, 3)
+            ]
+        );
+
+        // Every rotation and its reverse settles to the same book.
+        for shift in 0..bids.len() {
+            let mut order = bids.to_vec();
+            order.rotate_left(shift);
+            assert_eq!(summary(&book_from(4, &order)), expected);
+            order.reverse();
+            assert_eq!(summary(&book_from(4, &order)), expected);
+        }
+    }
+
+    #[test]
+    fn unit_book_partially_fills_the_marginal_bid() {
+        let book = book_from(4, &[entry(1, 500, 3), entry(2, 300, 2), entry(3, 200, 1)]);
+
+        assert_eq!(book.entries.len(), 2);
+        assert_eq!(book.units_allocated(4), 4);
+        assert_eq!(book.marginal_price(), 300);
+        assert_eq!(book.allocation(&entry(1, 0, 0).bidder, 4), 3);
+        assert_eq!(book.allocation(&entry(2, 0, 0).bidder, 4), 1);
+        assert_eq!(book.allocation(&entry(3, 0, 0).bidder, 4), 0);
+    }
 }
@@ -1,6 +1,12 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use solana_sha256_hasher::hashv;
 
+use crate::constants::VAULT_SEED;
 use crate::errors::AuctionError;
+use crate::state::{AuctionHouse, SealedBid};
 
 /// Moves lamports from the program-owned vault PDA to a recipient.
 /// The vault is program-owned, so direct lamport mutation is valid.
@@ -30,3 +36,239 @@ pub fn transfer_from_vault<'info>(
 
     Ok(())
 }
+
+/// Hash a bidder commits to while bidding is open: H(amount || salt || bidder).
+pub fn bid_commitment(amount: u64, salt: &[u8; 32], bidder: &Pubkey) -> [u8; 32] {
+    hashv(&[&amount.to_le_bytes(), salt.as_ref(), bidder.as_ref()]).to_bytes()
+}
+
+/// Multi-unit bid commitment: H(unit_price || quantity || salt || bidder).
+pub fn unit_bid_commitment(
+    unit_price: u64,
+    quantity: u32,
+    salt: &[u8; 32],
+    bidder: &Pubkey,
+) -> [u8; 32] {
+    hashv(&[
+        &unit_price.to_le_bytes(),
+        &quantity.to_le_bytes(),
+        salt.as_ref(),
+        bidder.as_ref(),
+    ])
+    .to_bytes()
+}
+
+/// Hash the authority commits to for a sealed reserve: H(price || salt || auction).
+pub fn reserve_commitment(reserve_price: u64, salt: &[u8; 32], auction: &Pubkey) -> [u8; 32] {
+    hashv(&[
+        &reserve_price.to_le_bytes(),
+        salt.as_ref(),
+        auction.as_ref(),
+    ])
+    .to_bytes()
+}
+
+/// SPL accounts used to move tokens between a user and a vault-owned account.
+pub struct VaultTokenAccounts<'a, 'info> {
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub user_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Resolves the optional payment token accounts of a context. Lamport auctions
+/// ignore them and get `None`; SPL auctions require all of them for the mint.
+pub fn token_payment<'a, 'info>(
+    auction: &AuctionHouse,
+    mint: &'a Option<InterfaceAccount<'info, Mint>>,
+    vault_token_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    user_token_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    token_program: &'a Option<Interface<'info, TokenInterface>>,
+) -> Result<Option<VaultTokenAccounts<'a, 'info>>> {
+    if !auction.is_token_denominated() {
+        return Ok(None);
+    }
+
+    let (Some(mint), Some(vault_token_account), Some(user_token_account), Some(token_program)) =
+        (mint, vault_token_account, user_token_account, token_program)
+    else {
+        return err!(AuctionError::MissingTokenAccounts);
+    };
+    require_keys_eq!(
+        mint.key(),
+        auction.payment_mint,
+        AuctionError::PaymentMintMismatch
+    );
+    require_keys_eq!(
+        user_token_account.mint,
+        auction.payment_mint,
+        AuctionError::PaymentMintMismatch
+    );
+
+    Ok(Some(VaultTokenAccounts {
+        mint,
+        vault_token_account,
+        user_token_account,
+        token_program,
+    }))
+}
+
+/// Tops up a bidder's escrow so it covers `target`, charging only the shortfall
+/// and crediting what the vault received. Payment accounts are resolved only
+/// when a top-up is due, so a bidder already covered can omit them.
+pub fn top_up_escrow<'a, 'info>(
+    sealed_bid: &mut SealedBid,
+    target: u64,
+    bidder: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    payment: impl FnOnce() -> Result<Option<VaultTokenAccounts<'a, 'info>>>,
+) -> Result<()>
+where
+    'info: 'a,
+{
+    if target > sealed_bid.deposited {
+        let delta = target
+            .checked_sub(sealed_bid.deposited)
+            .ok_or(AuctionError::MathOverflow)?;
+        let received = deposit_to_vault(bidder, vault, system_program, payment()?, delta)?;
+        sealed_bid.deposited = sealed_bid
+            .deposited
+            .checked_add(received)
+            .ok_or(AuctionError::MathOverflow)?;
+    }
+    require!(
+        sealed_bid.deposited >= target,
+        AuctionError::InsufficientDeposit
+    );
+
+    Ok(())
+}
+
+/// Escrows `amount` from `payer` into the auction vault and returns what the
+/// vault actually received. Token-2022 transfer fees are withheld from the
+/// vault side, so the credited amount can be lower than `amount`.
+pub fn deposit_to_vault<'info>(
+    payer: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    payment: Option<VaultTokenAccounts<'_, 'info>>,
+    amount: u64,
+) -> Result<u64> {
+    let Some(payment) = payment else {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: vault.clone(),
+                },
+            ),
+            amount,
+        )?;
+        return Ok(amount);
+    };
+
+    transfer_tokens_to_vault(payer, &payment, amount)
+}
+
+/// Pays `amount` out of the auction vault, in lamports or in the payment token.
+pub fn payout_from_vault<'info>(
+    auction_key: &Pubkey,
+    auction: &AuctionHouse,
+    vault: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    payment: Option<VaultTokenAccounts<'_, 'info>>,
+    amount: u64,
+) -> Result<()> {
+    match payment {
+        Some(payment) => transfer_tokens_from_vault(auction_key, auction, vault, &payment, amount),
+        None => transfer_from_vault(vault, recipient, amount),
+    }
+}
+
+/// Moves tokens from a user into a vault-owned token account and returns the
+/// amount the vault actually received. Token-2022 transfer fees are withheld
+/// from the receiving side, so the result can be lower than `amount`.
+pub fn transfer_tokens_to_vault<'info>(
+    authority: &AccountInfo<'info>,
+    accounts: &VaultTokenAccounts<'_, 'info>,
+    amount: u64,
+) -> Result<u64> {
+    let vault_token_info = accounts.vault_token_account.to_account_info();
+    let balance_before = token_balance(&vault_token_info)?;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.user_token_account.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                to: vault_token_info.clone(),
+                authority: authority.clone(),
+            },
+        ),
+        amount,
+        accounts.mint.decimals,
+    )?;
+
+    token_balance(&vault_token_info)?
+        .checked_sub(balance_before)
+        .ok_or_else(|| error!(AuctionError::MathOverflow))
+}
+
+/// Moves tokens out of a vault-owned token account, signing as the vault PDA.
+pub fn transfer_tokens_from_vault<'info>(
+    auction_key: &Pubkey,
+    auction: &AuctionHouse,
+    vault: &AccountInfo<'info>,
+    accounts: &VaultTokenAccounts<'_, 'info>,
+    amount: u64,
+) -> Result<()> {
+    require!(
+        accounts.vault_token_account.amount >= amount,
+        AuctionError::InsufficientVaultBalance
+    );
+    let vault_seeds: &[&[u8]] = &[VAULT_SEED, auction_key.as_ref(), &[auction.vault_bump]];
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.vault_token_account.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                to: accounts.user_token_account.to_account_info(),
+                authority: vault.clone(),
+            },
+            &[vault_seeds],
+        ),
+        amount,
+        accounts.mint.decimals,
+    )
+}
+
+/// Closes a vault-owned token account, sending its rent to `destination`.
+/// The account must already be empty.
+pub fn close_vault_token_account<'info>(
+    auction_key: &Pubkey,
+    auction: &AuctionHouse,
+    vault: &AccountInfo<'info>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    let vault_seeds: &[&[u8]] = &[VAULT_SEED, auction_key.as_ref(), &[auction.vault_bump]];
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: token_account.to_account_info(),
+            destination: destination.clone(),
+            authority: vault.clone(),
+        },
+        &[vault_seeds],
+    ))
+}
+
+/// Reads the current balance of a token account after CPIs have touched it.
+fn token_balance(token_account: &AccountInfo) -> Result<u64> {
+    let data = token_account.try_borrow_data()?;
+    Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
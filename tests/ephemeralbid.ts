@@ -1,16 +1,2148 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
+import { createHash, randomBytes } from "crypto";
+import { assert } from "chai";
+import {
+  Connection,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+} from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  TransferFee,
+  createMint,
+  createTokenAccount,
+  mintTo,
+  tokenBalance,
+} from "./token";
 import { Ephemeralbid } from "../target/types/ephemeralbid";
 
+const sleep = (ms: number) =>
+  new Promise((resolve) => setTimeout(resolve, ms));
+
 describe("ephemeralbid", () => {
   // Configure the client to use the local cluster.
   anchor.setProvider(anchor.AnchorProvider.env());
 
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
   const program = anchor.workspace.ephemeralbid as Program<Ephemeralbid>;
+  const authority = provider.wallet.publicKey;
+
+  const BID_DURATION = 10;
+  const REVEAL_DURATION = 30;
+
+  const noTokenAccounts = {
+    paymentMint: null,
+    vaultTokenAccount: null,
+    tokenProgram: null,
+  };
+
+  const auctionPda = (auctionId: anchor.BN) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("auction"),
+        authority.toBuffer(),
+        auctionId.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+
+  const bidPda = (auction: PublicKey, bidder: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("bid"), auction.toBuffer(), bidder.toBuffer()],
+      program.programId
+    )[0];
+
+  // Mirrors utils::bid_commitment: sha256(amount_le || salt || bidder).
+  const bidCommitment = (amount: anchor.BN, salt: Buffer, bidder: PublicKey) =>
+    Array.from(
+      createHash("sha256")
+        .update(amount.toArrayLike(Buffer, "le", 8))
+        .update(salt)
+        .update(bidder.toBuffer())
+        .digest()
+    );
+
+  // Mirrors utils::unit_bid_commitment:
+  // sha256(unit_price_le || quantity_le || salt || bidder).
+  const unitBidCommitment = (
+    unitPrice: anchor.BN,
+    quantity: number,
+    salt: Buffer,
+    bidder: PublicKey
+  ) => {
+    const quantityLe = Buffer.alloc(4);
+    quantityLe.writeUInt32LE(quantity);
+    return Array.from(
+      createHash("sha256")
+        .update(unitPrice.toArrayLike(Buffer, "le", 8))
+        .update(quantityLe)
+        .update(salt)
+        .update(bidder.toBuffer())
+        .digest()
+    );
+  };
+
+  // Mirrors utils::reserve_commitment: sha256(price_le || salt || auction).
+  const reserveCommitment = (
+    price: anchor.BN,
+    salt: Buffer,
+    auction: PublicKey
+  ) =>
+    Array.from(
+      createHash("sha256")
+        .update(price.toArrayLike(Buffer, "le", 8))
+        .update(salt)
+        .update(auction.toBuffer())
+        .digest()
+    );
+
+  const fundedBidder = async () => {
+    const bidder = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      bidder.publicKey,
+      2 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig, "confirmed");
+    return bidder;
+  };
+
+  const createAuction = async (
+    tieBreak: object,
+    overrides: { auctionId?: anchor.BN } & Record<string, unknown> = {},
+    accounts: object = {}
+  ) => {
+    const auctionId = overrides.auctionId ?? new anchor.BN(Date.now());
+    await program.methods
+      .createAuction({
+        auctionId,
+        minBid: new anchor.BN(1_000_000),
+        minIncrement: new anchor.BN(0),
+        startTime: new anchor.BN(0),
+        duration: new anchor.BN(BID_DURATION),
+        revealDuration: new anchor.BN(REVEAL_DURATION),
+        auctionType: { firstPrice: {} },
+        tieBreak,
+        unitCount: 0,
+        startPrice: new anchor.BN(0),
+        priceDecrement: new anchor.BN(0),
+        decayInterval: new anchor.BN(0),
+        buyNowPrice: new anchor.BN(0),
+        withdrawalPolicy: { forbidden: {} },
+        withdrawalPenaltyBps: 0,
+        itemAmount: new anchor.BN(0),
+        reserveCommitment: Array(32).fill(0),
+        extensionWindow: new anchor.BN(0),
+        extensionDuration: new anchor.BN(0),
+        maxExtension: new anchor.BN(0),
+        erValidator: PublicKey.default,
+        commitFrequencyMs: 0,
+        proceedsSplits: [],
+        ...overrides,
+      })
+      .accounts({
+        authority,
+        ...noTokenAccounts,
+        itemMint: null,
+        itemVault: null,
+        sellerItemAccount: null,
+        itemTokenProgram: null,
+        unitBook: null,
+        ...accounts,
+      })
+      .rpc();
+    return auctionPda(auctionId);
+  };
+
+  // `tokens` holds the payment accounts of token-denominated auctions.
+  const depositToEscrow = async (
+    auction: PublicKey,
+    bidder: Keypair,
+    amount: anchor.BN,
+    tokens: object = {}
+  ) =>
+    program.methods
+      .depositToEscrow(amount)
+      .accounts({
+        auctionHouse: auction,
+        bidder: bidder.publicKey,
+        bidderTokenAccount: null,
+        ...noTokenAccounts,
+        ...tokens,
+      })
+      .signers([bidder])
+      .rpc();
+
+  // Registers, funds, submits and commits a sealed bid on L1.
+  const placeCommitment = async (
+    auction: PublicKey,
+    bidder: Keypair,
+    deposit: anchor.BN,
+    commitment: number[],
+    tokens: object = {}
+  ) => {
+    await program.methods
+      .initializeSealedBid()
+      .accounts({ auctionHouse: auction, bidder: bidder.publicKey })
+      .signers([bidder])
+      .rpc();
+    await depositToEscrow(auction, bidder, deposit, tokens);
+    await program.methods
+      .submitSealedBid(commitment)
+      .accounts({
+        auctionHouse: auction,
+        sealedBid: bidPda(auction, bidder.publicKey),
+        session: null,
+        signer: bidder.publicKey,
+      })
+      .signers([bidder])
+      .rpc();
+    await program.methods
+      .commitBidL1()
+      .accounts({ auctionHouse: auction, bidder: bidder.publicKey })
+      .signers([bidder])
+      .rpc();
+  };
+
+  // Places and commits a sealed bid on L1, returning the reveal preimage.
+  const placeBid = async (
+    auction: PublicKey,
+    bidder: Keypair,
+    amount: anchor.BN
+  ) => {
+    const salt = randomBytes(32);
+    await placeCommitment(
+      auction,
+      bidder,
+      amount,
+      bidCommitment(amount, salt, bidder.publicKey)
+    );
+    return { bidder, amount, salt };
+  };
+
+  const revealBid = async (
+    auction: PublicKey,
+    bid: { bidder: Keypair; amount: anchor.BN; salt: Buffer }
+  ) =>
+    program.methods
+      .revealBid(bid.amount, Array.from(bid.salt))
+      .accounts({ auctionHouse: auction, bidder: bid.bidder.publicKey })
+      .signers([bid.bidder])
+      .rpc();
+
+  const settle = async (
+    auction: PublicKey,
+    bidder: PublicKey,
+    unitBook: PublicKey | null = null
+  ) =>
+    program.methods
+      .settleCommittedBid()
+      .accounts({
+        auctionHouse: auction,
+        sealedBid: bidPda(auction, bidder),
+        unitBook,
+      })
+      .rpc();
+
+  const vaultPda = (auction: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), auction.toBuffer()],
+      program.programId
+    )[0];
+
+  const configPda = PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  )[0];
+
+  const balance = async (account: PublicKey) =>
+    provider.connection.getBalance(account);
+
+  const vaultBalance = async (auction: PublicKey) =>
+    balance(vaultPda(auction));
+
+  const finalize = async (
+    auction: PublicKey,
+    unitBook: PublicKey | null = null
+  ) =>
+    program.methods
+      .finalizeAuction()
+      .accounts({ auctionHouse: auction, unitBook, authority })
+      .rpc();
+
+  const claimRefund = async (
+    auction: PublicKey,
+    bidder: Keypair,
+    tokens: object = {}
+  ) =>
+    program.methods
+      .claimRefund()
+      .accounts({
+        auctionHouse: auction,
+        bidder: bidder.publicKey,
+        bidderTokenAccount: null,
+        ...noTokenAccounts,
+        ...tokens,
+      })
+      .signers([bidder])
+      .rpc();
+
+  // Amount the vault paid out while running `action`.
+  const vaultPayout = async (
+    auction: PublicKey,
+    action: () => Promise<unknown>
+  ) => {
+    const before = await vaultBalance(auction);
+    await action();
+    return before - (await vaultBalance(auction));
+  };
+
+  const PERMISSION_PROGRAM_ID = new PublicKey(
+    "ACLseoPoyC3cBqoUtkbjZ4aDrkurZW86v19pXz2XQnp1"
+  );
+  const DELEGATION_PROGRAM_ID = new PublicKey(
+    "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+  );
+
+  const pda = (seeds: Buffer[], programId: PublicKey) =>
+    PublicKey.findProgramAddressSync(seeds, programId)[0];
+
+  const permissionPda = (sealedBid: PublicKey) =>
+    pda(
+      [Buffer.from("permission:"), sealedBid.toBuffer()],
+      PERMISSION_PROGRAM_ID
+    );
+
+  const delegateBid = async (
+    auction: PublicKey,
+    bidder: Keypair,
+    validator: PublicKey
+  ) => {
+    const sealedBid = bidPda(auction, bidder.publicKey);
+    return program.methods
+      .delegateBid()
+      .accounts({
+        auctionHouse: auction,
+        sealedBid,
+        permission: permissionPda(sealedBid),
+        validator,
+        bidder: bidder.publicKey,
+      })
+      .signers([bidder])
+      .rpc();
+  };
+
+  // Sealed bids only delegate behind a delegated permission, so this creates
+  // and delegates the bid's permission first.
+  const delegateSealedBid = async (
+    auction: PublicKey,
+    bidder: Keypair,
+    validator: PublicKey
+  ) => {
+    const sealedBid = bidPda(auction, bidder.publicKey);
+    const permission = permissionPda(sealedBid);
+    await program.methods
+      .createBidPermission()
+      .accounts({
+        auctionHouse: auction,
+        sealedBid,
+        permission,
+        bidder: bidder.publicKey,
+      })
+      .signers([bidder])
+      .rpc();
+    await program.methods
+      .delegateBidPermission()
+      .accounts({
+        auctionHouse: auction,
+        sealedBid,
+        permission,
+        bufferPermission: pda(
+          [Buffer.from("buffer"), permission.toBuffer()],
+          PERMISSION_PROGRAM_ID
+        ),
+        delegationRecordPermission: pda(
+          [Buffer.from("delegation"), permission.toBuffer()],
+          DELEGATION_PROGRAM_ID
+        ),
+        delegationMetadataPermission: pda(
+          [Buffer.from("delegation-metadata"), permission.toBuffer()],
+          DELEGATION_PROGRAM_ID
+        ),
+        validator,
+        bidder: bidder.publicKey,
+      })
+      .signers([bidder])
+      .rpc();
+    await delegateBid(auction, bidder, validator);
+  };
+
+  describe("second price", () => {
+    it("charges the runner-up bid and refunds the difference", async () => {
+      const [alice, bob, carol] = await Promise.all([
+        fundedBidder(),
+        fundedBidder(),
+        fundedBidder(),
+      ]);
+      const contested = await createAuction(
+        { earliestCommit: {} },
+        { auctionType: { secondPrice: {} } }
+      );
+      const uncontested = await createAuction(
+        { earliestCommit: {} },
+        { auctionType: { secondPrice: {} } }
+      );
+
+      const aliceBid = await placeBid(
+        contested,
+        alice,
+        new anchor.BN(5_000_000)
+      );
+      const bobBid = await placeBid(contested, bob, new anchor.BN(3_000_000));
+      const carolBid = await placeBid(
+        uncontested,
+        carol,
+        new anchor.BN(4_000_000)
+      );
+
+      await sleep(BID_DURATION * 1000);
+      await revealBid(contested, aliceBid);
+      await revealBid(contested, bobBid);
+      await revealBid(uncontested, carolBid);
+      await settle(contested, alice.publicKey);
+      await settle(contested, bob.publicKey);
+      await settle(uncontested, carol.publicKey);
+
+      await sleep(REVEAL_DURATION * 1000);
+      await finalize(contested);
+      await finalize(uncontested);
+
+      const contestedState = await program.account.auctionHouse.fetch(
+        contested
+      );
+      assert.ok(contestedState.winner.equals(alice.publicKey));
+      assert.ok(contestedState.clearingPrice.eqn(3_000_000));
+      assert.equal(
+        await vaultPayout(contested, () => claimRefund(contested, alice)),
+        2_000_000
+      );
+      assert.equal(
+        await vaultPayout(contested, () => claimRefund(contested, bob)),
+        3_000_000
+      );
+
+      // A lone bidder has no runner-up and pays the minimum bid.
+      const uncontestedState = await program.account.auctionHouse.fetch(
+        uncontested
+      );
+      assert.ok(uncontestedState.winner.equals(carol.publicKey));
+      assert.ok(uncontestedState.clearingPrice.eqn(1_000_000));
+      assert.equal(
+        await vaultPayout(uncontested, () => claimRefund(uncontested, carol)),
+        3_000_000
+      );
+    });
+
+    it("never clears below a revealed reserve", async () => {
+      const [alice, bob] = await Promise.all([fundedBidder(), fundedBidder()]);
+      // The commitment binds the auction key, so derive it up front.
+      const auctionId = new anchor.BN(Date.now());
+      const auction = auctionPda(auctionId);
+      const reservePrice = new anchor.BN(2_000_000);
+      const reserveSalt = randomBytes(32);
+      await createAuction(
+        { earliestCommit: {} },
+        {
+          auctionId,
+          auctionType: { secondPrice: {} },
+          reserveCommitment: reserveCommitment(
+            reservePrice,
+            reserveSalt,
+            auction
+          ),
+        }
+      );
+
+      const aliceBid = await placeBid(auction, alice, new anchor.BN(3_000_000));
+      const bobBid = await placeBid(auction, bob, new anchor.BN(1_000_000));
+
+      await sleep(BID_DURATION * 1000);
+      await revealBid(auction, aliceBid);
+      await revealBid(auction, bobBid);
+      await settle(auction, alice.publicKey);
+      await settle(auction, bob.publicKey);
+      await program.methods
+        .revealReserve(reservePrice, Array.from(reserveSalt))
+        .accounts({ auctionHouse: auction, authority })
+        .rpc();
+
+      await sleep(REVEAL_DURATION * 1000);
+      await finalize(auction);
+
+      // Bob's runner-up bid is below the reserve, so Alice pays the reserve.
+      const state = await program.account.auctionHouse.fetch(auction);
+      assert.deepEqual(state.outcome, { sold: {} });
+      assert.ok(state.winner.equals(alice.publicKey));
+      assert.ok(state.clearingPrice.eq(reservePrice));
+      assert.equal(
+        await vaultPayout(auction, () => claimRefund(auction, alice)),
+        1_000_000
+      );
+    });
+
+    it("treats a reserve left sealed as not met", async () => {
+      const alice = await fundedBidder();
+      const auctionId = new anchor.BN(Date.now());
+      const auction = auctionPda(auctionId);
+      const reservePrice = new anchor.BN(2_000_000);
+      const reserveSalt = randomBytes(32);
+      await createAuction(
+        { earliestCommit: {} },
+        {
+          auctionId,
+          reserveCommitment: reserveCommitment(
+            reservePrice,
+            reserveSalt,
+            auction
+          ),
+        }
+      );
+
+      const bid = await placeBid(auction, alice, new anchor.BN(3_000_000));
+      await sleep(BID_DURATION * 1000);
+      await revealBid(auction, bid);
+      await settle(auction, alice.publicKey);
+      await sleep(REVEAL_DURATION * 1000);
+
+      // Too late to reveal the reserve once the reveal window has closed.
+      try {
+        await program.methods
+          .revealReserve(reservePrice, Array.from(reserveSalt))
+          .accounts({ auctionHouse: auction, authority })
+          .rpc();
+        assert.fail("the reserve can't be revealed after the window");
+      } catch (err) {
+        assert.include(String(err), "RevealWindowClosed");
+      }
+
+      // Alice beat the reserve, but it was never revealed.
+      await finalize(auction);
+      const state = await program.account.auctionHouse.fetch(auction);
+      assert.deepEqual(state.outcome, { reserveNotMet: {} });
+      assert.equal(
+        await vaultPayout(auction, () => claimRefund(auction, alice)),
+        3_000_000
+      );
+    });
+  });
+
+  describe("tie-breaking", () => {
+    const amount = new anchor.BN(5_000_000);
+
+    for (const [name, tieBreak] of [
+      ["earliest commit", { earliestCommit: {} }],
+      ["lowest bidder key", { lowestBidderKey: {} }],
+    ] as const) {
+      it(`same winner in any settlement order (${name})`, async () => {
+        const [alice, bob] = await Promise.all([
+          fundedBidder(),
+          fundedBidder(),
+        ]);
+        const forward = await createAuction(tieBreak);
+        const reverse = await createAuction(tieBreak);
+
+        // Alice commits a full second before Bob on both auctions, so the
+        // reverse order has to replace a provisional winner to be correct.
+        const bids = [];
+        for (const bidder of [alice, bob]) {
+          for (const auction of [forward, reverse]) {
+            bids.push({
+              auction,
+              bid: await placeBid(auction, bidder, amount),
+            });
+          }
+          await sleep(1100);
+        }
+
+        await sleep(BID_DURATION * 1000);
+        for (const { auction, bid } of bids) {
+          await revealBid(auction, bid);
+        }
+
+        await settle(forward, alice.publicKey);
+        await settle(forward, bob.publicKey);
+        await settle(reverse, bob.publicKey);
+        await settle(reverse, alice.publicKey);
+
+        const forwardState = await program.account.auctionHouse.fetch(forward);
+        const reverseState = await program.account.auctionHouse.fetch(reverse);
+        assert.ok(forwardState.winner.equals(reverseState.winner));
+        assert.ok(forwardState.highestBid.eq(amount));
+        assert.ok(forwardState.secondHighestBid.eq(amount));
+
+        // The winner must be the one the documented rule selects.
+        const candidates = await Promise.all(
+          [alice, bob].map(async (bidder) => {
+            const bid = await program.account.sealedBid.fetch(
+              bidPda(forward, bidder.publicKey)
+            );
+            return {
+              key: bidder.publicKey,
+              committedAt: bid.committedAt.toNumber(),
+            };
+          })
+        );
+        const byKey = (a: PublicKey, b: PublicKey) =>
+          Buffer.compare(a.toBuffer(), b.toBuffer());
+        candidates.sort((a, b) =>
+          "earliestCommit" in tieBreak && a.committedAt !== b.committedAt
+            ? a.committedAt - b.committedAt
+            : byKey(a.key, b.key)
+        );
+        assert.ok(forwardState.winner.equals(candidates[0].key));
+      });
+    }
+  });
+
+  describe("batch settlement", () => {
+    const settleBatch = async (auction: PublicKey, bids: PublicKey[]) =>
+      program.methods
+        .settleCommittedBids()
+        .accounts({ auctionHouse: auction, unitBook: null })
+        .remainingAccounts(
+          bids.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }))
+        )
+        .rpc();
+
+    it("settles a batch and rejects foreign or oversized ones", async () => {
+      const [alice, bob, carol] = await Promise.all([
+        fundedBidder(),
+        fundedBidder(),
+        fundedBidder(),
+      ]);
+      const auction = await createAuction({ earliestCommit: {} });
+      const other = await createAuction({ earliestCommit: {} });
+
+      const aliceBid = await placeBid(auction, alice, new anchor.BN(3_000_000));
+      const bobBid = await placeBid(auction, bob, new anchor.BN(2_000_000));
+      const carolBid = await placeBid(other, carol, new anchor.BN(4_000_000));
+      const aliceKey = bidPda(auction, alice.publicKey);
+      const bobKey = bidPda(auction, bob.publicKey);
+
+      await sleep(BID_DURATION * 1000);
+      await revealBid(auction, aliceBid);
+      await revealBid(auction, bobBid);
+      await revealBid(other, carolBid);
+
+      try {
+        await settleBatch(auction, [aliceKey, bidPda(other, carol.publicKey)]);
+        assert.fail("a bid from another auction should be rejected");
+      } catch (err) {
+        assert.include(String(err), "BidAuctionMismatch");
+      }
+
+      try {
+        await settleBatch(auction, Array(21).fill(aliceKey));
+        assert.fail("a batch over MAX_SETTLE_BATCH should be rejected");
+      } catch (err) {
+        assert.include(String(err), "InvalidBatchSize");
+      }
+
+      // Alice appears twice; the second entry sees her bid already settled.
+      await settleBatch(auction, [aliceKey, bobKey, aliceKey]);
+      const state = await program.account.auctionHouse.fetch(auction);
+      assert.equal(state.settledCount, 2);
+      assert.equal(state.settledCount, state.bidderCount);
+      assert.ok(state.winner.equals(alice.publicKey));
+      assert.ok(state.highestBid.eqn(3_000_000));
+
+      await sleep(REVEAL_DURATION * 1000);
+      await finalize(auction);
+      const finalized = await program.account.auctionHouse.fetch(auction);
+      assert.deepEqual(finalized.outcome, { sold: {} });
+    });
+  });
+
+  describe("multi-unit", () => {
+    const UNIT_COUNT = 4;
+    const UNIT_SIZE = 1_000;
+
+    const seededPda = (seed: string, auction: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from(seed), auction.toBuffer()],
+        program.programId
+      )[0];
+
+    // Lists UNIT_COUNT lots of a fresh SPL mint held by the authority.
+    const createUnitAuction = async () => {
+      const itemMint = await createMint(provider, 0);
+      const sellerItemAccount = await createTokenAccount(
+        provider,
+        itemMint,
+        authority
+      );
+      await mintTo(
+        provider,
+        itemMint,
+        sellerItemAccount,
+        UNIT_COUNT * UNIT_SIZE
+      );
+
+      const auctionId = new anchor.BN(Date.now());
+      const auction = auctionPda(auctionId);
+      const unitBook = seededPda("unit_book", auction);
+      const itemVault = seededPda("item_vault", auction);
+      await createAuction(
+        { earliestCommit: {} },
+        {
+          auctionId,
+          auctionType: { multiUnit: {} },
+          unitCount: UNIT_COUNT,
+          itemAmount: new anchor.BN(UNIT_COUNT * UNIT_SIZE),
+        },
+        {
+          itemMint,
+          itemVault,
+          sellerItemAccount,
+          itemTokenProgram: TOKEN_PROGRAM_ID,
+          unitBook,
+        }
+      );
+      return { auction, unitBook, itemMint, itemVault, sellerItemAccount };
+    };
+
+    const placeUnitBid = async (
+      auction: PublicKey,
+      bidder: Keypair,
+      unitPrice: anchor.BN,
+      quantity: number,
+      deposit: anchor.BN
+    ) => {
+      const salt = randomBytes(32);
+      await placeCommitment(
+        auction,
+        bidder,
+        deposit,
+        unitBidCommitment(unitPrice, quantity, salt, bidder.publicKey)
+      );
+      return { bidder, unitPrice, quantity, salt };
+    };
+
+    const revealUnitBid = async (
+      auction: PublicKey,
+      bid: {
+        bidder: Keypair;
+        unitPrice: anchor.BN;
+        quantity: number;
+        salt: Buffer;
+      }
+    ) =>
+      program.methods
+        .revealUnitBid(bid.unitPrice, bid.quantity, Array.from(bid.salt))
+        .accounts({ auctionHouse: auction, bidder: bid.bidder.publicKey })
+        .signers([bid.bidder])
+        .rpc();
+
+    const itemBalance = (account: PublicKey) => tokenBalance(provider, account);
+
+    it("partly fills the marginal bid and refunds the excess", async () => {
+      const [alice, bob, carol] = await Promise.all([
+        fundedBidder(),
+        fundedBidder(),
+        fundedBidder(),
+      ]);
+      const { auction, unitBook, itemMint, itemVault } =
+        await createUnitAuction();
+
+      // Alice takes 3 lots, Bob's 2-lot bid gets the last one and sets the
+      // clearing price, and Carol is priced out.
+      const bids = [
+        await placeUnitBid(
+          auction,
+          alice,
+          new anchor.BN(2_000_000),
+          3,
+          new anchor.BN(7_000_000)
+        ),
+        await placeUnitBid(
+          auction,
+          bob,
+          new anchor.BN(1_500_000),
+          2,
+          new anchor.BN(3_000_000)
+        ),
+        await placeUnitBid(
+          auction,
+          carol,
+          new anchor.BN(1_000_000),
+          1,
+          new anchor.BN(1_000_000)
+        ),
+      ];
+
+      await sleep(BID_DURATION * 1000);
+      for (const bid of bids) {
+        await revealUnitBid(auction, bid);
+      }
+      for (const { bidder } of bids) {
+        await settle(auction, bidder.publicKey, unitBook);
+      }
+
+      await sleep(REVEAL_DURATION * 1000);
+      await finalize(auction, unitBook);
+      const state = await program.account.auctionHouse.fetch(auction);
+      assert.ok(state.clearingPrice.eqn(1_500_000));
+      assert.equal(state.unitsSold, UNIT_COUNT);
+
+      // refund = deposit - clearing_price * units won
+      for (const [bidder, units, refund] of [
+        [alice, 3, 7_000_000 - 1_500_000 * 3],
+        [bob, 1, 3_000_000 - 1_500_000],
+        [carol, 0, 1_000_000],
+      ] as const) {
+        const recipientItemAccount = await createTokenAccount(
+          provider,
+          itemMint,
+          bidder.publicKey
+        );
+        const paid = await vaultPayout(auction, () =>
+          program.methods
+            .claimUnits()
+            .accounts({
+              auctionHouse: auction,
+              unitBook,
+              bidder: bidder.publicKey,
+              bidderTokenAccount: null,
+              ...noTokenAccounts,
+              itemMint,
+              itemVault,
+              recipientItemAccount,
+              itemTokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([bidder])
+            .rpc()
+        );
+        assert.equal(paid, refund);
+        assert.equal(
+          await itemBalance(recipientItemAccount),
+          units * UNIT_SIZE
+        );
+      }
+    });
+
+    it("returns unsold lots to the authority", async () => {
+      const dave = await fundedBidder();
+      const { auction, unitBook, itemMint, itemVault, sellerItemAccount } =
+        await createUnitAuction();
+
+      const bid = await placeUnitBid(
+        auction,
+        dave,
+        new anchor.BN(1_000_000),
+        1,
+        new anchor.BN(1_000_000)
+      );
+      await sleep(BID_DURATION * 1000);
+      await revealUnitBid(auction, bid);
+      await settle(auction, dave.publicKey, unitBook);
+      await sleep(REVEAL_DURATION * 1000);
+      await finalize(auction, unitBook);
+
+      const state = await program.account.auctionHouse.fetch(auction);
+      assert.equal(state.unitsSold, 1);
+      await program.methods
+        .claimItem()
+        .accounts({
+          auctionHouse: auction,
+          itemMint,
+          itemVault,
+          recipientItemAccount: sellerItemAccount,
+          claimer: authority,
+          itemTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      assert.equal(
+        await itemBalance(sellerItemAccount),
+        (UNIT_COUNT - 1) * UNIT_SIZE
+      );
+      assert.equal(await itemBalance(itemVault), UNIT_SIZE);
+    });
+  });
+
+  describe("token payments", () => {
+    // Lists an auction escrowed in a fresh mint of `programId`.
+    const tokenAuction = async (
+      programId: PublicKey,
+      overrides: Record<string, unknown>,
+      transferFee?: TransferFee
+    ) => {
+      const paymentMint = await createMint(provider, 6, programId, transferFee);
+      const auctionId = new anchor.BN(Date.now());
+      const auction = auctionPda(auctionId);
+      const vaultTokenAccount = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault_token"), auction.toBuffer()],
+        program.programId
+      )[0];
+      const tokens = {
+        paymentMint,
+        vaultTokenAccount,
+        tokenProgram: programId,
+      };
+      await createAuction(
+        { earliestCommit: {} },
+        { auctionId, ...overrides },
+        tokens
+      );
+      const holder = async (owner: PublicKey, amount = 0) => {
+        const account = await createTokenAccount(
+          provider,
+          paymentMint,
+          owner,
+          programId,
+          transferFee !== undefined
+        );
+        if (amount > 0) {
+          await mintTo(provider, paymentMint, account, amount, programId);
+        }
+        return account;
+      };
+      return { auction, tokens, holder };
+    };
+
+    // Claims the proceeds into a fresh authority token account and returns
+    // the platform fee charged, which is zero unless an earlier run left a
+    // fee configured.
+    const claimTokenProceeds = async (
+      auction: PublicKey,
+      tokens: object,
+      holder: (owner: PublicKey) => Promise<PublicKey>
+    ) => {
+      const state = await program.account.auctionHouse.fetch(auction);
+      const config =
+        state.feeBps > 0
+          ? await program.account.protocolConfig.fetch(configPda)
+          : null;
+      const authorityTokenAccount = await holder(authority);
+      await program.methods
+        .claimSellerProceeds()
+        .accounts({
+          config: config ? configPda : null,
+          feeRecipient: null,
+          feeRecipientTokenAccount: config
+            ? await holder(config.feeRecipient)
+            : null,
+          auctionHouse: auction,
+          authorityTokenAccount,
+          ...tokens,
+          authority,
+        })
+        .rpc();
+      const gross = state.clearingPrice.toNumber();
+      return {
+        authorityTokenAccount,
+        net: gross - Math.floor((gross * state.feeBps) / 10_000),
+      };
+    };
+
+    it("tops up SPL escrow by transfer and pays out of the vault", async () => {
+      const [alice, bob] = await Promise.all([fundedBidder(), fundedBidder()]);
+      const { auction, tokens, holder } = await tokenAuction(
+        TOKEN_PROGRAM_ID,
+        { auctionType: { english: {} } }
+      );
+      const aliceTokens = await holder(alice.publicKey, 5_000_000);
+      const bobTokens = await holder(bob.publicKey, 5_000_000);
+
+      for (const [bidder, bidderTokenAccount, amount] of [
+        [alice, aliceTokens, 2_000_000],
+        [bob, bobTokens, 2_500_000],
+      ] as const) {
+        await program.methods
+          .initializeSealedBid()
+          .accounts({ auctionHouse: auction, bidder: bidder.publicKey })
+          .signers([bidder])
+          .rpc();
+        // Nothing is escrowed yet, so the bid tops up the whole amount.
+        await program.methods
+          .placeOpenBid(new anchor.BN(amount))
+          .accounts({
+            auctionHouse: auction,
+            bidder: bidder.publicKey,
+            bidderTokenAccount,
+            ...tokens,
+          })
+          .signers([bidder])
+          .rpc();
+      }
+      assert.equal(
+        await tokenBalance(provider, tokens.vaultTokenAccount),
+        4_500_000
+      );
+      assert.equal(await tokenBalance(provider, aliceTokens), 3_000_000);
+      assert.equal(await tokenBalance(provider, bobTokens), 2_500_000);
+
+      // Outbid, Alice takes her escrow back before the auction ends.
+      await claimRefund(auction, alice, {
+        bidderTokenAccount: aliceTokens,
+        ...tokens,
+      });
+      assert.equal(await tokenBalance(provider, aliceTokens), 5_000_000);
+
+      await sleep((BID_DURATION + REVEAL_DURATION) * 1000);
+      await finalize(auction);
+      const { authorityTokenAccount, net } = await claimTokenProceeds(
+        auction,
+        tokens,
+        holder
+      );
+      assert.equal(await tokenBalance(provider, authorityTokenAccount), net);
+      assert.equal(await tokenBalance(provider, tokens.vaultTokenAccount), 0);
+    });
+
+    it("credits Token-2022 escrow net of the transfer fee", async () => {
+      const FEE_BPS = 100;
+      // Token-2022 withholds the fee, rounded up, from what arrives.
+      const received = (amount: number) =>
+        amount - Math.ceil((amount * FEE_BPS) / 10_000);
+
+      const alice = await fundedBidder();
+      const { auction, tokens, holder } = await tokenAuction(
+        TOKEN_2022_PROGRAM_ID,
+        {},
+        { bps: FEE_BPS, maxFee: 1_000_000_000 }
+      );
+      const aliceTokens = await holder(alice.publicKey, 5_000_000);
+      const aliceAccounts = { bidderTokenAccount: aliceTokens, ...tokens };
+
+      const amount = new anchor.BN(2_000_000);
+      const salt = randomBytes(32);
+      await placeCommitment(
+        auction,
+        alice,
+        new anchor.BN(3_000_000),
+        bidCommitment(amount, salt, alice.publicKey),
+        aliceAccounts
+      );
+      // The escrow is credited with what the vault received.
+      const escrowed = received(3_000_000);
+      const bid = await program.account.sealedBid.fetch(
+        bidPda(auction, alice.publicKey)
+      );
+      assert.ok(bid.deposited.eqn(escrowed));
+      assert.equal(
+        await tokenBalance(provider, tokens.vaultTokenAccount),
+        escrowed
+      );
+
+      await sleep(BID_DURATION * 1000);
+      await revealBid(auction, { bidder: alice, amount, salt });
+      await settle(auction, alice.publicKey);
+      await sleep(REVEAL_DURATION * 1000);
+      await finalize(auction);
+
+      const refund = escrowed - amount.toNumber();
+      await claimRefund(auction, alice, aliceAccounts);
+      assert.equal(
+        await tokenBalance(provider, aliceTokens),
+        5_000_000 - 3_000_000 + received(refund)
+      );
+
+      const { authorityTokenAccount, net } = await claimTokenProceeds(
+        auction,
+        tokens,
+        holder
+      );
+      assert.equal(
+        await tokenBalance(provider, authorityTokenAccount),
+        received(net)
+      );
+      assert.equal(await tokenBalance(provider, tokens.vaultTokenAccount), 0);
+    });
+  });
+
+  describe("item escrow", () => {
+    // Mints a one-off item to the authority; the accounts go to
+    // `createAuction` to escrow it.
+    const escrowedItem = async (auction: PublicKey) => {
+      const itemMint = await createMint(provider, 0);
+      const sellerItemAccount = await createTokenAccount(
+        provider,
+        itemMint,
+        authority
+      );
+      await mintTo(provider, itemMint, sellerItemAccount, 1);
+      const itemVault = PublicKey.findProgramAddressSync(
+        [Buffer.from("item_vault"), auction.toBuffer()],
+        program.programId
+      )[0];
+      return {
+        itemMint,
+        itemVault,
+        sellerItemAccount,
+        itemTokenProgram: TOKEN_PROGRAM_ID,
+      };
+    };
+
+    const claimItem = (
+      auction: PublicKey,
+      item: { itemMint: PublicKey; itemVault: PublicKey },
+      claimer: Keypair | null,
+      recipientItemAccount: PublicKey
+    ) =>
+      program.methods
+        .claimItem()
+        .accounts({
+          auctionHouse: auction,
+          itemMint: item.itemMint,
+          itemVault: item.itemVault,
+          recipientItemAccount,
+          claimer: claimer ? claimer.publicKey : authority,
+          itemTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers(claimer ? [claimer] : [])
+        .rpc();
+
+    const closeAuction = (auction: PublicKey, itemVault: PublicKey) =>
+      program.methods
+        .closeAuction()
+        .accounts({
+          auctionHouse: auction,
+          ...noTokenAccounts,
+          authorityTokenAccount: null,
+          itemVault,
+          itemTokenProgram: TOKEN_PROGRAM_ID,
+          authority,
+        })
+        .rpc();
+
+    it("closes only once proceeds, item and escrows are out", async () => {
+      const alice = await fundedBidder();
+      const auctionId = new anchor.BN(Date.now());
+      const auction = auctionPda(auctionId);
+      const item = await escrowedItem(auction);
+      await createAuction(
+        { earliestCommit: {} },
+        { auctionId, itemAmount: new anchor.BN(1) },
+        item
+      );
+
+      // Alice escrows more than she bids, so a refund stays owed to her.
+      const amount = new anchor.BN(2_000_000);
+      const salt = randomBytes(32);
+      await placeCommitment(
+        auction,
+        alice,
+        new anchor.BN(3_000_000),
+        bidCommitment(amount, salt, alice.publicKey)
+      );
+      await sleep(BID_DURATION * 1000);
+      await revealBid(auction, { bidder: alice, amount, salt });
+      await settle(auction, alice.publicKey);
+      await sleep(REVEAL_DURATION * 1000);
+      await finalize(auction);
+
+      const rejectClose = async (error: string) => {
+        try {
+          await closeAuction(auction, item.itemVault);
+          assert.fail(`closing should fail with ${error}`);
+        } catch (err) {
+          assert.include(String(err), error);
+        }
+      };
+
+      await rejectClose("ProceedsNotClaimed");
+      const config = await program.account.protocolConfig.fetchNullable(
+        configPda
+      );
+      await program.methods
+        .claimSellerProceeds()
+        .accounts({
+          config: config ? configPda : null,
+          feeRecipient: config ? config.feeRecipient : null,
+          feeRecipientTokenAccount: null,
+          auctionHouse: auction,
+          authorityTokenAccount: null,
+          ...noTokenAccounts,
+          authority,
+        })
+        .rpc();
+
+      await rejectClose("ItemNotClaimed");
+      const aliceItemAccount = await createTokenAccount(
+        provider,
+        item.itemMint,
+        alice.publicKey
+      );
+      await claimItem(auction, item, alice, aliceItemAccount);
+      assert.equal(await tokenBalance(provider, aliceItemAccount), 1);
+
+      await rejectClose("OutstandingBids");
+      assert.equal(
+        await vaultPayout(auction, () => claimRefund(auction, alice)),
+        1_000_000
+      );
+      await program.methods
+        .closeSealedBid()
+        .accounts({ auctionHouse: auction, bidder: alice.publicKey })
+        .signers([alice])
+        .rpc();
+
+      await closeAuction(auction, item.itemVault);
+      assert.isNull(await provider.connection.getAccountInfo(auction));
+      assert.isNull(await provider.connection.getAccountInfo(item.itemVault));
+    });
+
+    it("returns the item to the seller when the reserve is not met", async () => {
+      const alice = await fundedBidder();
+      const auctionId = new anchor.BN(Date.now());
+      const auction = auctionPda(auctionId);
+      const item = await escrowedItem(auction);
+      const reservePrice = new anchor.BN(3_000_000);
+      const reserveSalt = randomBytes(32);
+      await createAuction(
+        { earliestCommit: {} },
+        {
+          auctionId,
+          itemAmount: new anchor.BN(1),
+          reserveCommitment: reserveCommitment(
+            reservePrice,
+            reserveSalt,
+            auction
+          ),
+        },
+        item
+      );
+      assert.equal(await tokenBalance(provider, item.itemVault), 1);
+
+      const bid = await placeBid(auction, alice, new anchor.BN(2_000_000));
+      await sleep(BID_DURATION * 1000);
+      await revealBid(auction, bid);
+      await settle(auction, alice.publicKey);
+      await program.methods
+        .revealReserve(reservePrice, Array.from(reserveSalt))
+        .accounts({ auctionHouse: auction, authority })
+        .rpc();
+      await sleep(REVEAL_DURATION * 1000);
+      await finalize(auction);
+
+      const state = await program.account.auctionHouse.fetch(auction);
+      assert.deepEqual(state.outcome, { reserveNotMet: {} });
+
+      // The highest bidder did not win, so only the seller can take the item.
+      const aliceItemAccount = await createTokenAccount(
+        provider,
+        item.itemMint,
+        alice.publicKey
+      );
+      try {
+        await claimItem(auction, item, alice, aliceItemAccount);
+        assert.fail("the item should stay with the seller");
+      } catch (err) {
+        assert.include(String(err), "ItemClaimNotAllowed");
+      }
+      await claimItem(auction, item, null, item.sellerItemAccount);
+      assert.equal(await tokenBalance(provider, item.sellerItemAccount), 1);
+      assert.equal(await tokenBalance(provider, item.itemVault), 0);
+      assert.equal(
+        await vaultPayout(auction, () => claimRefund(auction, alice)),
+        2_000_000
+      );
+    });
+  });
+
+  describe("buy it now", () => {
+    it("ends the auction and refunds the other bidders", async () => {
+      const [alice, bob] = await Promise.all([fundedBidder(), fundedBidder()]);
+      const price = new anchor.BN(50_000_000);
+      const auction = await createAuction(
+        { earliestCommit: {} },
+        { buyNowPrice: price }
+      );
+      const aliceBid = await placeBid(
+        auction,
+        alice,
+        new anchor.BN(5_000_000)
+      );
+
+      await program.methods
+        .initializeSealedBid()
+        .accounts({ auctionHouse: auction, bidder: bob.publicKey })
+        .signers([bob])
+        .rpc();
+      await program.methods
+        .buyNow()
+        .accounts({
+          auctionHouse: auction,
+          bidder: bob.publicKey,
+          bidderTokenAccount: null,
+          ...noTokenAccounts,
+        })
+        .signers([bob])
+        .rpc();
+
+      const state = await program.account.auctionHouse.fetch(auction);
+      assert.ok(state.winner.equals(bob.publicKey));
+      assert.ok(state.clearingPrice.eq(price));
+      assert.isTrue(state.finalized);
+      assert.deepEqual(state.outcome, { boughtNow: {} });
+
+      try {
+        await program.methods
+          .submitSealedBid(
+            bidCommitment(aliceBid.amount, aliceBid.salt, alice.publicKey)
+          )
+          .accounts({
+            auctionHouse: auction,
+            sealedBid: bidPda(auction, alice.publicKey),
+            session: null,
+            signer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc();
+        assert.fail("bidding should be closed after a buy-it-now");
+      } catch (err) {
+        assert.include(String(err), "AuctionBoughtOut");
+      }
+
+      await program.methods
+        .claimRefund()
+        .accounts({
+          auctionHouse: auction,
+          bidder: alice.publicKey,
+          bidderTokenAccount: null,
+          ...noTokenAccounts,
+        })
+        .signers([alice])
+        .rpc();
+      const refunded = await program.account.sealedBid.fetch(
+        bidPda(auction, alice.publicKey)
+      );
+      assert.isTrue(refunded.refundClaimed);
+    });
+  });
+
+  describe("withdrawal", () => {
+    const withdraw = (auction: PublicKey, bidder: Keypair) =>
+      program.methods
+        .withdrawBid()
+        .accounts({
+          auctionHouse: auction,
+          bidder: bidder.publicKey,
+          bidderTokenAccount: null,
+          ...noTokenAccounts,
+        })
+        .signers([bidder])
+        .rpc();
+
+    it("keeps deposits locked under the default policy", async () => {
+      const alice = await fundedBidder();
+      const auction = await createAuction({ earliestCommit: {} });
+      await placeBid(auction, alice, new anchor.BN(2_000_000));
+      try {
+        await withdraw(auction, alice);
+        assert.fail("withdrawal is forbidden by default");
+      } catch (err) {
+        assert.include(String(err), "WithdrawalNotAllowed");
+      }
+    });
+
+    it("keeps the penalty for the seller on withdrawal", async () => {
+      try {
+        await createAuction(
+          { earliestCommit: {} },
+          { withdrawalPolicy: { allowedWithPenalty: {} } }
+        );
+        assert.fail("a penalty policy needs a penalty");
+      } catch (err) {
+        assert.include(String(err), "InvalidWithdrawalPolicy");
+      }
+
+      const alice = await fundedBidder();
+      const auction = await createAuction(
+        { earliestCommit: {} },
+        {
+          withdrawalPolicy: { allowedWithPenalty: {} },
+          withdrawalPenaltyBps: 1_000,
+        }
+      );
+      await placeBid(auction, alice, new anchor.BN(2_000_000));
+
+      assert.equal(
+        await vaultPayout(auction, () => withdraw(auction, alice)),
+        1_800_000
+      );
+      const state = await program.account.auctionHouse.fetch(auction);
+      assert.ok(state.forfeitedAmount.eqn(200_000));
+      const bid = await program.account.sealedBid.fetch(
+        bidPda(auction, alice.publicKey)
+      );
+      assert.ok(bid.deposited.eqn(0));
+      assert.isFalse(bid.committed);
+    });
+  });
+
+  describe("dutch", () => {
+    const acceptDutchPrice = (auction: PublicKey, bidder: Keypair) =>
+      program.methods
+        .acceptDutchPrice()
+        .accounts({
+          auctionHouse: auction,
+          bidder: bidder.publicKey,
+          bidderTokenAccount: null,
+          ...noTokenAccounts,
+        })
+        .signers([bidder])
+        .rpc();
+
+    it("sells to the first taker at the decayed price", async () => {
+      const START_PRICE = 5_000_000;
+      const DECREMENT = 1_000_000;
+      const schedule = {
+        auctionType: { dutch: {} },
+        startPrice: new anchor.BN(START_PRICE),
+        priceDecrement: new anchor.BN(DECREMENT),
+        decayInterval: new anchor.BN(2),
+      };
+      try {
+        await createAuction(
+          { earliestCommit: {} },
+          { ...schedule, startPrice: new anchor.BN(1_000_000) }
+        );
+        assert.fail("the start price has to sit above the minimum bid");
+      } catch (err) {
+        assert.include(String(err), "InvalidDutchSchedule");
+      }
+
+      const [alice, bob] = await Promise.all([fundedBidder(), fundedBidder()]);
+      const auction = await createAuction({ earliestCommit: {} }, schedule);
+      for (const bidder of [alice, bob]) {
+        await program.methods
+          .initializeSealedBid()
+          .accounts({ auctionHouse: auction, bidder: bidder.publicKey })
+          .signers([bidder])
+          .rpc();
+      }
+
+      await sleep(5 * 1000);
+      const before = await vaultBalance(auction);
+      await acceptDutchPrice(auction, alice);
+      const state = await program.account.auctionHouse.fetch(auction);
+      const price = state.clearingPrice.toNumber();
+      assert.deepEqual(state.outcome, { sold: {} });
+      assert.ok(state.winner.equals(alice.publicKey));
+      // The ask drops in whole steps and never below the minimum bid.
+      assert.isBelow(price, START_PRICE);
+      assert.isAtLeast(price, 1_000_000);
+      assert.equal((START_PRICE - price) % DECREMENT, 0);
+      assert.equal((await vaultBalance(auction)) - before, price);
+
+      try {
+        await acceptDutchPrice(auction, bob);
+        assert.fail("only the first taker buys");
+      } catch (err) {
+        assert.include(String(err), "AuctionFinalized");
+      }
+    });
+  });
+
+  describe("scheduled start", () => {
+    it("takes no bids before the start time", async () => {
+      const now = Math.floor(Date.now() / 1000);
+      try {
+        await createAuction(
+          { earliestCommit: {} },
+          { startTime: new anchor.BN(now - 60) }
+        );
+        assert.fail("a start time in the past should be rejected");
+      } catch (err) {
+        assert.include(String(err), "InvalidStartTime");
+      }
+
+      const alice = await fundedBidder();
+      const startTime = new anchor.BN(now + 5);
+      const auction = await createAuction(
+        { earliestCommit: {} },
+        { startTime }
+      );
+      const state = await program.account.auctionHouse.fetch(auction);
+      assert.ok(state.startTime.eq(startTime));
+      assert.ok(state.endTime.eq(startTime.addn(BID_DURATION)));
+
+      const register = () =>
+        program.methods
+          .initializeSealedBid()
+          .accounts({ auctionHouse: auction, bidder: alice.publicKey })
+          .signers([alice])
+          .rpc();
+      try {
+        await register();
+        assert.fail("registration should wait for the start time");
+      } catch (err) {
+        assert.include(String(err), "AuctionNotStarted");
+      }
+
+      await sleep(8 * 1000);
+      await register();
+      const bid = await program.account.sealedBid.fetch(
+        bidPda(auction, alice.publicKey)
+      );
+      assert.ok(bid.bidder.equals(alice.publicKey));
+    });
+  });
+
+  describe("english", () => {
+    const openBid = async (
+      auction: PublicKey,
+      bidder: Keypair,
+      amount: number
+    ) =>
+      program.methods
+        .placeOpenBid(new anchor.BN(amount))
+        .accounts({
+          auctionHouse: auction,
+          bidder: bidder.publicKey,
+          bidderTokenAccount: null,
+          ...noTokenAccounts,
+        })
+        .signers([bidder])
+        .rpc();
+
+    const register = (auction: PublicKey, bidder: Keypair) =>
+      program.methods
+        .initializeSealedBid()
+        .accounts({ auctionHouse: auction, bidder: bidder.publicKey })
+        .signers([bidder])
+        .rpc();
+
+    const cancel = (auction: PublicKey) =>
+      program.methods
+        .cancelAuction()
+        .accounts({ auctionHouse: auction, authority })
+        .rpc();
+
+    it("lets an outbid bidder withdraw before the auction ends", async () => {
+      const [alice, bob] = await Promise.all([fundedBidder(), fundedBidder()]);
+      const auction = await createAuction(
+        { earliestCommit: {} },
+        {
+          auctionType: { english: {} },
+          minIncrement: new anchor.BN(500_000),
+        }
+      );
+      for (const bidder of [alice, bob]) {
+        await program.methods
+          .initializeSealedBid()
+          .accounts({ auctionHouse: auction, bidder: bidder.publicKey })
+          .signers([bidder])
+          .rpc();
+      }
+
+      await openBid(auction, alice, 2_000_000);
+      try {
+        await openBid(auction, bob, 2_200_000);
+        assert.fail("bid under the increment should be rejected");
+      } catch (err) {
+        assert.include(String(err), "BidIncrementTooSmall");
+      }
+      await openBid(auction, bob, 2_500_000);
+
+      const state = await program.account.auctionHouse.fetch(auction);
+      assert.ok(state.winner.equals(bob.publicKey));
+      assert.ok(state.highestBid.eqn(2_500_000));
+
+      try {
+        await claimRefund(auction, bob);
+        assert.fail("the leader should not be able to withdraw");
+      } catch (err) {
+        assert.include(String(err), "LeaderCannotWithdraw");
+      }
+      await claimRefund(auction, alice);
+      const aliceBid = await program.account.sealedBid.fetch(
+        bidPda(auction, alice.publicKey)
+      );
+      assert.isTrue(aliceBid.refundClaimed);
+    });
+
+    it("cancels before the first bid and refunds escrow", async () => {
+      const alice = await fundedBidder();
+      const auction = await createAuction(
+        { earliestCommit: {} },
+        { auctionType: { english: {} } }
+      );
+      await register(auction, alice);
+      await depositToEscrow(auction, alice, new anchor.BN(2_000_000));
+
+      await cancel(auction);
+      const state = await program.account.auctionHouse.fetch(auction);
+      assert.deepEqual(state.outcome, { cancelled: {} });
+      assert.isTrue(state.finalized);
+
+      try {
+        await openBid(auction, alice, 2_000_000);
+        assert.fail("a cancelled auction should not take bids");
+      } catch (err) {
+        assert.include(String(err), "AuctionCancelled");
+      }
+      assert.equal(
+        await vaultPayout(auction, () => claimRefund(auction, alice)),
+        2_000_000
+      );
+    });
+
+    it("can't be cancelled once it has a leader", async () => {
+      const alice = await fundedBidder();
+      const auction = await createAuction(
+        { earliestCommit: {} },
+        { auctionType: { english: {} } }
+      );
+      await register(auction, alice);
+      await openBid(auction, alice, 2_000_000);
+
+      try {
+        await cancel(auction);
+        assert.fail("the leader's bid should block cancellation");
+      } catch (err) {
+        assert.include(String(err), "CancelNotAllowed");
+      }
+      const state = await program.account.auctionHouse.fetch(auction);
+      assert.deepEqual(state.outcome, { pending: {} });
+    });
+
+    it("extends on late bids up to the hard end", async () => {
+      const softClose = {
+        extensionWindow: new anchor.BN(BID_DURATION),
+        extensionDuration: new anchor.BN(5),
+        maxExtension: new anchor.BN(8),
+      };
+      // Moving end_time on a sealed submit would leak when bids arrive.
+      try {
+        await createAuction({ earliestCommit: {} }, softClose);
+        assert.fail("sealed auctions should not soft close");
+      } catch (err) {
+        assert.include(String(err), "InvalidExtension");
+      }
+
+      const [alice, bob] = await Promise.all([fundedBidder(), fundedBidder()]);
+      const auction = await createAuction(
+        { earliestCommit: {} },
+        { auctionType: { english: {} }, ...softClose }
+      );
+      const listed = await program.account.auctionHouse.fetch(auction);
+      assert.ok(listed.hardEndTime.eq(listed.endTime.addn(8)));
+      await register(auction, alice);
+      await register(auction, bob);
+
+      // The window spans the whole auction, so every bid lands in it.
+      await openBid(auction, alice, 2_000_000);
+      let state = await program.account.auctionHouse.fetch(auction);
+      assert.ok(state.endTime.eq(listed.endTime.addn(5)));
+      await openBid(auction, bob, 2_500_000);
+      state = await program.account.auctionHouse.fetch(auction);
+      assert.ok(state.endTime.eq(listed.hardEndTime));
+
+      await sleep((BID_DURATION + 8 + 1) * 1000);
+      try {
+        await openBid(auction, alice, 3_000_000);
+        assert.fail("no bid should land after the hard end");
+      } catch (err) {
+        assert.include(String(err), "AuctionEnded");
+      }
+    });
+  });
+
+  describe("seller proceeds", () => {
+    const programData = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    )[0];
+
+    const claimProceeds = async (
+      auction: PublicKey,
+      feeRecipient: PublicKey | null,
+      splitAccounts: PublicKey[] = []
+    ) =>
+      program.methods
+        .claimSellerProceeds()
+        .accounts({
+          config: feeRecipient ? configPda : null,
+          feeRecipient,
+          feeRecipientTokenAccount: null,
+          auctionHouse: auction,
+          authorityTokenAccount: null,
+          ...noTokenAccounts,
+          authority,
+        })
+        .remainingAccounts(
+          splitAccounts.map((pubkey) => ({
+            pubkey,
+            isWritable: true,
+            isSigner: false,
+          }))
+        )
+        .rpc();
+
+    // Sets the fee, creating the config unless an earlier run already did.
+    const setFee = async (feeBps: number, feeRecipient: PublicKey) => {
+      if (await program.account.protocolConfig.fetchNullable(configPda)) {
+        await program.methods
+          .updateConfig(authority, feeBps)
+          .accounts({ config: configPda, feeRecipient, admin: authority })
+          .rpc();
+      } else {
+        await program.methods
+          .initializeConfig(feeBps)
+          .accounts({
+            config: configPda,
+            program: program.programId,
+            programData,
+            feeRecipient,
+            admin: authority,
+          })
+          .rpc();
+      }
+    };
+
+    // Runs one first-price auction to a finalized sale at `amount`.
+    const sellFor = async (auction: PublicKey, amount: anchor.BN) => {
+      const bid = await placeBid(auction, await fundedBidder(), amount);
+      await sleep(BID_DURATION * 1000);
+      await revealBid(auction, bid);
+      await settle(auction, bid.bidder.publicKey);
+      await sleep(REVEAL_DURATION * 1000);
+      await finalize(auction);
+    };
+
+    it("charges the fee fixed at listing", async () => {
+      // An unfunded recipient could not take a fee below the rent minimum.
+      try {
+        await setFee(250, Keypair.generate().publicKey);
+        assert.fail("the fee recipient must be rent exempt");
+      } catch (err) {
+        assert.include(String(err), "FeeRecipientNotRentExempt");
+      }
+
+      const feeRecipient = (await fundedBidder()).publicKey;
+      await setFee(0, feeRecipient);
+      const feeless = await createAuction({ earliestCommit: {} });
+      await setFee(250, feeRecipient);
+      const feed = await createAuction({ earliestCommit: {} });
+      const feelessState = await program.account.auctionHouse.fetch(feeless);
+      const feedState = await program.account.auctionHouse.fetch(feed);
+      assert.equal(feelessState.feeBps, 0);
+      assert.equal(feedState.feeBps, 250);
+
+      await Promise.all([
+        sellFor(feeless, new anchor.BN(2_000_000)),
+        sellFor(feed, new anchor.BN(4_000_000)),
+      ]);
+
+      // Raising the fee after listing does not reprice the sale.
+      await setFee(1_000, feeRecipient);
+
+      const recipientBefore = await balance(feeRecipient);
+      const paid = await vaultPayout(feed, () =>
+        claimProceeds(feed, feeRecipient)
+      );
+      const fee = (await balance(feeRecipient)) - recipientBefore;
+      assert.equal(fee, 100_000);
+      assert.equal(paid - fee, 3_900_000);
+
+      // No fee is due, so the config and recipient can be left out.
+      assert.equal(
+        await vaultPayout(feeless, () => claimProceeds(feeless, null)),
+        2_000_000
+      );
+    });
+
+    it("pays each split and leaves the rounding to the authority", async () => {
+      const [creator, coOwner] = await Promise.all([
+        fundedBidder(),
+        fundedBidder(),
+      ]);
+      const auction = await createAuction(
+        { earliestCommit: {} },
+        {
+          proceedsSplits: [
+            { recipient: creator.publicKey, bps: 3_000 },
+            { recipient: coOwner.publicKey, bps: 1_500 },
+          ],
+        }
+      );
+      const gross = 1_000_007;
+      await sellFor(auction, new anchor.BN(gross));
+
+      const { feeRecipient } = await program.account.protocolConfig.fetch(
+        configPda
+      );
+      for (const splitAccounts of [
+        [creator.publicKey],
+        [coOwner.publicKey, creator.publicKey],
+      ]) {
+        try {
+          await claimProceeds(auction, feeRecipient, splitAccounts);
+          assert.fail("split accounts must match the auction's splits");
+        } catch (err) {
+          assert.include(String(err), "SplitRecipientMismatch");
+        }
+      }
+
+      const { feeBps } = await program.account.auctionHouse.fetch(auction);
+      const net = gross - Math.floor((gross * feeBps) / 10_000);
+      const creatorShare = Math.floor((net * 3_000) / 10_000);
+      const coOwnerShare = Math.floor((net * 1_500) / 10_000);
+
+      const payees = [feeRecipient, creator.publicKey, coOwner.publicKey];
+      const before = await Promise.all(payees.map(balance));
+      const paid = await vaultPayout(auction, () =>
+        claimProceeds(auction, feeRecipient, payees.slice(1))
+      );
+      const after = await Promise.all(payees.map(balance));
+      const [fee, creatorGain, coOwnerGain] = after.map(
+        (lamports, i) => lamports - before[i]
+      );
+      assert.equal(paid, gross);
+      assert.equal(fee, gross - net);
+      assert.equal(creatorGain, creatorShare);
+      assert.equal(coOwnerGain, coOwnerShare);
+
+      // The authority keeps what the rounded-down shares leave over.
+      const authorityShare = paid - fee - creatorGain - coOwnerGain;
+      assert.equal(authorityShare, net - creatorShare - coOwnerShare);
+      assert.isAbove(authorityShare, Math.floor((net * 5_500) / 10_000));
+    });
+
+    it("leaves a share the recipient can't receive to the authority", async () => {
+      // Unfunded, so a share below the rent-exempt minimum can't be paid.
+      const unfunded = Keypair.generate().publicKey;
+      const auction = await createAuction(
+        { earliestCommit: {} },
+        { proceedsSplits: [{ recipient: unfunded, bps: 1_000 }] }
+      );
+      const gross = 2_000_000;
+      await sellFor(auction, new anchor.BN(gross));
+
+      const { feeRecipient } = await program.account.protocolConfig.fetch(
+        configPda
+      );
+      const { feeBps } = await program.account.auctionHouse.fetch(auction);
+      const fee = Math.floor((gross * feeBps) / 10_000);
+      const authorityBefore = await balance(authority);
+      const paid = await vaultPayout(auction, () =>
+        claimProceeds(auction, feeRecipient, [unfunded])
+      );
+      assert.equal(paid, gross);
+      assert.equal(await balance(unfunded), 0);
+      // Less the fee and the transaction fee, all of it reaches the authority.
+      assert.approximately(
+        (await balance(authority)) - authorityBefore,
+        gross - fee,
+        10_000
+      );
+    });
+  });
+
+  describe("session keys", () => {
+    const sessionPda = (bidder: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("session"), bidder.toBuffer()],
+        program.programId
+      )[0];
+
+    it("lets a session key bid within its limit until revoked", async () => {
+      const alice = await fundedBidder();
+      const hotKey = Keypair.generate();
+      const auctions = [
+        await createAuction({ earliestCommit: {} }),
+        await createAuction({ earliestCommit: {} }),
+      ];
+      const session = sessionPda(alice.publicKey);
+
+      await program.methods
+        .createSession(
+          hotKey.publicKey,
+          new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+          new anchor.BN(2_000_000)
+        )
+        .accounts({ bidder: alice.publicKey })
+        .signers([alice])
+        .rpc();
+      for (const auction of auctions) {
+        await program.methods
+          .initializeSealedBid()
+          .accounts({ auctionHouse: auction, bidder: alice.publicKey })
+          .signers([alice])
+          .rpc();
+      }
+
+      const submitWithSession = (auction: PublicKey, amount: anchor.BN) =>
+        program.methods
+          .submitSealedBid(
+            bidCommitment(amount, randomBytes(32), alice.publicKey)
+          )
+          .accounts({
+            auctionHouse: auction,
+            sealedBid: bidPda(auction, alice.publicKey),
+            session,
+            signer: hotKey.publicKey,
+          })
+          .signers([hotKey])
+          .rpc();
+
+      const [first, second] = auctions;
+      const amount = new anchor.BN(1_500_000);
+      await depositToEscrow(first, alice, amount);
+      await submitWithSession(first, amount);
+      // Resubmitting against the same deposit is not charged again.
+      await submitWithSession(first, amount);
+      const { spent } = await program.account.sessionKey.fetch(session);
+      assert.ok(spent.eq(amount));
+
+      // Each bid is within the limit, but together they exceed it.
+      const minBid = new anchor.BN(1_000_000);
+      await depositToEscrow(second, alice, minBid);
+      try {
+        await submitWithSession(second, minBid);
+        assert.fail("the session's spend limit should apply");
+      } catch (err) {
+        assert.include(String(err), "SessionSpendExceeded");
+      }
+
+      await program.methods
+        .revokeSession()
+        .accounts({ bidder: alice.publicKey })
+        .signers([alice])
+        .rpc();
+      assert.isNull(await provider.connection.getAccountInfo(session));
+      try {
+        await submitWithSession(first, amount);
+        assert.fail("a revoked session key should be rejected");
+      } catch (err) {
+        assert.include(String(err), "AccountNotInitialized");
+      }
+    });
+  });
+
+  // Needs a rollup validator next to the local cluster, e.g.
+  // EPHEMERAL_PROVIDER_ENDPOINT=http://localhost:7799 and EPHEMERAL_VALIDATOR
+  // set to its identity (defaults to the devnet Asia validator).
+  const erEndpoint = process.env.EPHEMERAL_PROVIDER_ENDPOINT;
+  (erEndpoint ? describe : describe.skip)("ephemeral rollup", () => {
+    const validator = new PublicKey(
+      process.env.EPHEMERAL_VALIDATOR ??
+        "MAS1Dt9qreoRMQ14YQuhg8UTZMMzDdKhmkZMECCzk57"
+    );
+    const erProgram = () =>
+      new Program<Ephemeralbid>(
+        program.idl,
+        new anchor.AnchorProvider(
+          new Connection(erEndpoint!, "confirmed"),
+          provider.wallet
+        )
+      );
+
+    const waitForOwner = async (account: PublicKey, owner: PublicKey) => {
+      for (let i = 0; i < 60; i++) {
+        const info = await provider.connection.getAccountInfo(account);
+        if (info?.owner.equals(owner)) return;
+        await sleep(500);
+      }
+      assert.fail(`${account.toBase58()} was not returned to ${owner}`);
+    };
+
+    it("commits a delegated bid without writing the auction", async () => {
+      const alice = await fundedBidder();
+      const auction = await createAuction(
+        { earliestCommit: {} },
+        { erValidator: validator }
+      );
+      const amount = new anchor.BN(3_000_000);
+      const salt = randomBytes(32);
+      const sealedBid = bidPda(auction, alice.publicKey);
+
+      await program.methods
+        .initializeSealedBid()
+        .accounts({ auctionHouse: auction, bidder: alice.publicKey })
+        .signers([alice])
+        .rpc();
+      await depositToEscrow(auction, alice, amount);
+      await delegateSealedBid(auction, alice, validator);
+
+      // The auction stays on L1 and is only read by the rollup, and the bid
+      // is checked against the deposit escrowed there.
+      const er = erProgram();
+      await er.methods
+        .submitSealedBid(bidCommitment(amount, salt, alice.publicKey))
+        .accounts({
+          auctionHouse: auction,
+          sealedBid,
+          session: null,
+          signer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc();
+      await er.methods
+        .commitAndUndelegateBid()
+        .accounts({
+          auctionHouse: auction,
+          sealedBid,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc();
+      await waitForOwner(sealedBid, program.programId);
+
+      const bid = await program.account.sealedBid.fetch(sealedBid);
+      assert.ok("committed" in bid.status);
+      assert.isTrue(bid.committed);
+      let state = await program.account.auctionHouse.fetch(auction);
+      assert.equal(state.committedCount, 0);
+
+      await sleep(BID_DURATION * 1000);
+      await revealBid(auction, { bidder: alice, amount, salt });
+      await settle(auction, alice.publicKey);
+
+      state = await program.account.auctionHouse.fetch(auction);
+      assert.equal(state.committedCount, 1);
+      assert.equal(state.settledCount, 1);
+      assert.ok(state.winner.equals(alice.publicKey));
+    });
+
+    it("runs a delegated English auction on escrowed deposits", async () => {
+      const [alice, bob] = await Promise.all([fundedBidder(), fundedBidder()]);
+      const auction = await createAuction(
+        { earliestCommit: {} },
+        {
+          auctionType: { english: {} },
+          minIncrement: new anchor.BN(500_000),
+          erValidator: validator,
+        }
+      );
+      for (const bidder of [alice, bob]) {
+        await program.methods
+          .initializeSealedBid()
+          .accounts({ auctionHouse: auction, bidder: bidder.publicKey })
+          .signers([bidder])
+          .rpc();
+        await depositToEscrow(auction, bidder, new anchor.BN(3_000_000));
+      }
+      await program.methods
+        .delegateAuction()
+        .accounts({ auctionHouse: auction, validator, authority })
+        .rpc();
+
+      // Registration and funding happen on L1 before delegation only.
+      const carol = await fundedBidder();
+      for (const action of [
+        () =>
+          program.methods
+            .initializeSealedBid()
+            .accounts({ auctionHouse: auction, bidder: carol.publicKey })
+            .signers([carol])
+            .rpc(),
+        () => depositToEscrow(auction, alice, new anchor.BN(1_000_000)),
+      ]) {
+        try {
+          await action();
+          assert.fail("a delegated auction should not take registrations");
+        } catch (err) {
+          assert.include(String(err), "AuctionDelegated");
+        }
+      }
+
+      const er = erProgram();
+      const escrowedBid = (bidder: Keypair, amount: number) =>
+        er.methods
+          .placeEscrowedBid(new anchor.BN(amount))
+          .accounts({ auctionHouse: auction, bidder: bidder.publicKey })
+          .signers([bidder])
+          .rpc();
+      await escrowedBid(alice, 2_000_000);
+      await escrowedBid(bob, 2_500_000);
+      try {
+        await escrowedBid(alice, 3_500_000);
+        assert.fail("a bid above the escrowed deposit should be rejected");
+      } catch (err) {
+        assert.include(String(err), "InsufficientDeposit");
+      }
+
+      await sleep(BID_DURATION * 1000);
+      await er.methods
+        .undelegateAuction()
+        .accounts({ auctionHouse: auction, payer: authority })
+        .rpc();
+      await waitForOwner(auction, program.programId);
+
+      const state = await program.account.auctionHouse.fetch(auction);
+      assert.ok(state.winner.equals(bob.publicKey));
+      assert.ok(state.highestBid.eqn(2_500_000));
+    });
+  });
+
+  // Needs a private rollup (PER) validator; against a local stand-in, run the
+  // ephemeral validator next to a localnet that clones the delegation and
+  // permission programs (see [test.validator] in Anchor.toml).
+  const perEndpoint = process.env.PRIVATE_EPHEMERAL_PROVIDER_ENDPOINT;
+  (perEndpoint ? describe : describe.skip)("private ephemeral rollup", () => {
+    const validator = new PublicKey(
+      process.env.EPHEMERAL_VALIDATOR ??
+        "MAS1Dt9qreoRMQ14YQuhg8UTZMMzDdKhmkZMECCzk57"
+    );
+
+    it("hides a delegated bid from outsiders until bidding closes", async () => {
+      const alice = await fundedBidder();
+      const auction = await createAuction(
+        { earliestCommit: {} },
+        { erValidator: validator }
+      );
+      const amount = new anchor.BN(3_000_000);
+      const sealedBid = bidPda(auction, alice.publicKey);
+      const permission = permissionPda(sealedBid);
+
+      await program.methods
+        .initializeSealedBid()
+        .accounts({ auctionHouse: auction, bidder: alice.publicKey })
+        .signers([alice])
+        .rpc();
+      await depositToEscrow(auction, alice, amount);
+      try {
+        await delegateBid(auction, alice, validator);
+        assert.fail("a sealed bid needs a delegated permission first");
+      } catch (err) {
+        assert.include(String(err), "CannotDelegate");
+      }
+      await delegateSealedBid(auction, alice, validator);
+
+      // An unauthenticated client is not a member of the bid's permission.
+      const outsider = new Connection(perEndpoint!, "confirmed");
+      const peek = async () => {
+        try {
+          return await outsider.getAccountInfo(sealedBid);
+        } catch {
+          return null;
+        }
+      };
+      assert.isNull(await peek());
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+      await sleep(BID_DURATION * 1000);
+      const per = new Program<Ephemeralbid>(
+        program.idl,
+        new anchor.AnchorProvider(
+          new Connection(perEndpoint!, "confirmed"),
+          provider.wallet
+        )
+      );
+      await per.methods
+        .releaseBidPermission()
+        .accounts({
+          auctionHouse: auction,
+          sealedBid,
+          permission,
+          payer: authority,
+        })
+        .rpc();
+      assert.isNotNull(await peek());
+    });
   });
 });
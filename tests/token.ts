@@ -0,0 +1,173 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+
+// Minimal SPL Token / Token-2022 instructions for the tests, built by hand
+// so the suite only depends on web3.js.
+
+export const TOKEN_PROGRAM_ID = new PublicKey(
+  "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+);
+export const TOKEN_2022_PROGRAM_ID = new PublicKey(
+  "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+);
+
+// Base account length, after which Token-2022 stores the account type byte
+// and its TLV extensions.
+const TOKEN_ACCOUNT_LEN = 165;
+const MINT_LEN = 82;
+// Account type byte plus a TLV header (u16 type, u16 length).
+const EXTENSION_OVERHEAD = 1 + 4;
+const TRANSFER_FEE_CONFIG_LEN = 108;
+const TRANSFER_FEE_AMOUNT_LEN = 8;
+
+const INITIALIZE_ACCOUNT_3 = 18;
+const INITIALIZE_MINT_2 = 20;
+const MINT_TO = 7;
+const TRANSFER_FEE_EXTENSION = 26;
+
+const u16 = (value: number) => {
+  const buf = Buffer.alloc(2);
+  buf.writeUInt16LE(value);
+  return buf;
+};
+
+const u64 = (value: number) =>
+  new anchor.BN(value).toArrayLike(Buffer, "le", 8);
+
+const createAccount = async (
+  provider: anchor.AnchorProvider,
+  space: number,
+  programId: PublicKey,
+  init: (account: PublicKey) => TransactionInstruction[]
+) => {
+  const account = Keypair.generate();
+  const lamports =
+    await provider.connection.getMinimumBalanceForRentExemption(space);
+  const tx = new Transaction().add(
+    SystemProgram.createAccount({
+      fromPubkey: provider.wallet.publicKey,
+      newAccountPubkey: account.publicKey,
+      lamports,
+      space,
+      programId,
+    }),
+    ...init(account.publicKey)
+  );
+  await provider.sendAndConfirm(tx, [account]);
+  return account.publicKey;
+};
+
+export type TransferFee = { bps: number; maxFee: number };
+
+// Creates a mint whose mint authority is the provider wallet. A transfer fee
+// makes it a Token-2022 mint with the TransferFeeConfig extension.
+export const createMint = async (
+  provider: anchor.AnchorProvider,
+  decimals: number,
+  programId: PublicKey = TOKEN_PROGRAM_ID,
+  transferFee?: TransferFee
+) => {
+  const authority = provider.wallet.publicKey;
+  const space = transferFee
+    ? TOKEN_ACCOUNT_LEN + EXTENSION_OVERHEAD + TRANSFER_FEE_CONFIG_LEN
+    : MINT_LEN;
+  return createAccount(provider, space, programId, (mint) => [
+    ...(transferFee
+      ? [
+          new TransactionInstruction({
+            programId,
+            keys: [{ pubkey: mint, isSigner: false, isWritable: true }],
+            data: Buffer.concat([
+              Buffer.from([TRANSFER_FEE_EXTENSION, 0]),
+              Buffer.from([1]),
+              authority.toBuffer(),
+              Buffer.from([1]),
+              authority.toBuffer(),
+              u16(transferFee.bps),
+              u64(transferFee.maxFee),
+            ]),
+          }),
+        ]
+      : []),
+    new TransactionInstruction({
+      programId,
+      keys: [{ pubkey: mint, isSigner: false, isWritable: true }],
+      data: Buffer.concat([
+        Buffer.from([INITIALIZE_MINT_2, decimals]),
+        authority.toBuffer(),
+        Buffer.from([0]),
+      ]),
+    }),
+  ]);
+};
+
+// Creates a token account for `owner`. Accounts of a transfer-fee mint need
+// room for the TransferFeeAmount extension.
+export const createTokenAccount = async (
+  provider: anchor.AnchorProvider,
+  mint: PublicKey,
+  owner: PublicKey,
+  programId: PublicKey = TOKEN_PROGRAM_ID,
+  hasTransferFee = false
+) =>
+  createAccount(
+    provider,
+    hasTransferFee
+      ? TOKEN_ACCOUNT_LEN + EXTENSION_OVERHEAD + TRANSFER_FEE_AMOUNT_LEN
+      : TOKEN_ACCOUNT_LEN,
+    programId,
+    (account) => [
+      new TransactionInstruction({
+        programId,
+        keys: [
+          { pubkey: account, isSigner: false, isWritable: true },
+          { pubkey: mint, isSigner: false, isWritable: false },
+        ],
+        data: Buffer.concat([
+          Buffer.from([INITIALIZE_ACCOUNT_3]),
+          owner.toBuffer(),
+        ]),
+      }),
+    ]
+  );
+
+export const mintTo = async (
+  provider: anchor.AnchorProvider,
+  mint: PublicKey,
+  destination: PublicKey,
+  amount: number,
+  programId: PublicKey = TOKEN_PROGRAM_ID
+) =>
+  provider.sendAndConfirm(
+    new Transaction().add(
+      new TransactionInstruction({
+        programId,
+        keys: [
+          { pubkey: mint, isSigner: false, isWritable: true },
+          { pubkey: destination, isSigner: false, isWritable: true },
+          {
+            pubkey: provider.wallet.publicKey,
+            isSigner: true,
+            isWritable: false,
+          },
+        ],
+        data: Buffer.concat([Buffer.from([MINT_TO]), u64(amount)]),
+      })
+    )
+  );
+
+// Token amount held by a token account; it sits after the mint and owner.
+export const tokenBalance = async (
+  provider: anchor.AnchorProvider,
+  account: PublicKey
+) => {
+  const info = await provider.connection.getAccountInfo(account);
+  if (!info) throw new Error(`${account.toBase58()} does not exist`);
+  return new anchor.BN(info.data.subarray(64, 72), "le").toNumber();
+};